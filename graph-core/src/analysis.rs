@@ -0,0 +1,408 @@
+//! Standard graph algorithms over a [`WikiGraph`]'s link adjacency.
+//!
+//! These build on the public outlink/backlink API rather than reaching
+//! into `WikiGraph` internals, and treat every node — including
+//! implicitly-created stub pages — as a real node in the graph: a
+//! dangling reference is still part of the link structure for the
+//! purposes of pathfinding, cycle detection, and component analysis.
+
+use crate::graph::WikiGraph;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Find the shortest directed path from `from` to `to`, following
+/// outlinks only (BFS). Returns `None` if either page doesn't exist or
+/// no directed path connects them.
+pub fn shortest_path(graph: &WikiGraph, from: &str, to: &str) -> Option<Vec<String>> {
+    if !graph.page_exists(from) || !graph.page_exists(to) {
+        return None;
+    }
+    if from == to {
+        return Some(vec![from.to_string()]);
+    }
+
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<String> = VecDeque::new();
+    let mut prev: HashMap<String, String> = HashMap::new();
+
+    visited.insert(from.to_string());
+    queue.push_back(from.to_string());
+
+    while let Some(name) = queue.pop_front() {
+        for neighbor in graph.get_outlinks(&name) {
+            if !visited.insert(neighbor.clone()) {
+                continue;
+            }
+            prev.insert(neighbor.clone(), name.clone());
+
+            if neighbor == to {
+                let mut path = vec![neighbor.clone()];
+                let mut cur = neighbor;
+                while let Some(p) = prev.get(&cur) {
+                    path.push(p.clone());
+                    cur = p.clone();
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            queue.push_back(neighbor);
+        }
+    }
+
+    None
+}
+
+/// Find every strongly connected component of the link graph, via
+/// Tarjan's algorithm. Each component is a list of page names; singleton
+/// components (a page with no cycle through itself) are included too.
+pub fn strongly_connected_components(graph: &WikiGraph) -> Vec<Vec<String>> {
+    struct Tarjan<'g> {
+        graph: &'g WikiGraph,
+        index: HashMap<String, usize>,
+        lowlink: HashMap<String, usize>,
+        on_stack: HashSet<String>,
+        stack: Vec<String>,
+        next_index: usize,
+        components: Vec<Vec<String>>,
+    }
+
+    impl<'g> Tarjan<'g> {
+        fn visit(&mut self, name: &str) {
+            self.index.insert(name.to_string(), self.next_index);
+            self.lowlink.insert(name.to_string(), self.next_index);
+            self.next_index += 1;
+            self.stack.push(name.to_string());
+            self.on_stack.insert(name.to_string());
+
+            for neighbor in self.graph.get_outlinks(name) {
+                if !self.index.contains_key(&neighbor) {
+                    self.visit(&neighbor);
+                    let neighbor_low = self.lowlink[&neighbor];
+                    let entry = self.lowlink.get_mut(name).unwrap();
+                    *entry = (*entry).min(neighbor_low);
+                } else if self.on_stack.contains(&neighbor) {
+                    let neighbor_index = self.index[&neighbor];
+                    let entry = self.lowlink.get_mut(name).unwrap();
+                    *entry = (*entry).min(neighbor_index);
+                }
+            }
+
+            if self.lowlink[name] == self.index[name] {
+                let mut component = Vec::new();
+                loop {
+                    let member = self.stack.pop().unwrap();
+                    self.on_stack.remove(&member);
+                    let is_root = member == name;
+                    component.push(member);
+                    if is_root {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        graph,
+        index: HashMap::new(),
+        lowlink: HashMap::new(),
+        on_stack: HashSet::new(),
+        stack: Vec::new(),
+        next_index: 0,
+        components: Vec::new(),
+    };
+
+    for page in graph.list_pages() {
+        if !tarjan.index.contains_key(&page.name) {
+            tarjan.visit(&page.name);
+        }
+    }
+
+    tarjan.components
+}
+
+/// Whether the link graph contains a cycle.
+///
+/// A page linking to itself, or any strongly connected component with
+/// more than one page, counts as a cycle.
+pub fn is_cyclic(graph: &WikiGraph) -> bool {
+    strongly_connected_components(graph)
+        .iter()
+        .any(|component| component.len() > 1 || graph.get_outlinks(&component[0]).contains(&component[0]))
+}
+
+/// Names of every page with no incoming links, stub pages included.
+pub fn orphans(graph: &WikiGraph) -> Vec<String> {
+    graph
+        .list_pages()
+        .iter()
+        .filter(|page| graph.get_backlinks(&page.name).is_empty())
+        .map(|page| page.name.clone())
+        .collect()
+}
+
+/// Names of every page with no outgoing links, stub pages included.
+pub fn dead_ends(graph: &WikiGraph) -> Vec<String> {
+    graph
+        .list_pages()
+        .iter()
+        .filter(|page| graph.get_outlinks(&page.name).is_empty())
+        .map(|page| page.name.clone())
+        .collect()
+}
+
+/// Names of every page with neither backlinks nor outlinks: entirely
+/// disconnected from the rest of the graph. Stricter than [`orphans`]
+/// (zero backlinks only) and [`dead_ends`] (zero outlinks only).
+pub fn orphan_pages(graph: &WikiGraph) -> Vec<String> {
+    graph
+        .list_pages()
+        .iter()
+        .filter(|page| {
+            graph.get_backlinks(&page.name).is_empty() && graph.get_outlinks(&page.name).is_empty()
+        })
+        .map(|page| page.name.clone())
+        .collect()
+}
+
+/// Every connected component of the link graph's *undirected* projection:
+/// groups of pages that are mutually reachable once link direction is
+/// ignored. Unlike [`strongly_connected_components`], a one-way link is
+/// enough to put both pages in the same group. Each page appears in
+/// exactly one group; singletons (no links at all) get their own
+/// component.
+pub fn connected_components(graph: &WikiGraph) -> Vec<Vec<String>> {
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut components: Vec<Vec<String>> = Vec::new();
+
+    for page in graph.list_pages() {
+        if visited.contains(&page.name) {
+            continue;
+        }
+
+        let mut component = Vec::new();
+        let mut queue: VecDeque<String> = VecDeque::new();
+        queue.push_back(page.name.clone());
+        visited.insert(page.name.clone());
+
+        while let Some(name) = queue.pop_front() {
+            let neighbors: Vec<String> = graph
+                .get_outlinks(&name)
+                .into_iter()
+                .chain(graph.get_backlinks(&name))
+                .collect();
+            component.push(name);
+
+            for neighbor in neighbors {
+                if visited.insert(neighbor.clone()) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        components.push(component);
+    }
+
+    components
+}
+
+/// Every directed cycle in the link graph: strongly connected components
+/// with more than one page, plus singleton components that link to
+/// themselves. Unlike [`is_cyclic`], this returns the actual cycles
+/// instead of just whether one exists.
+pub fn find_cycles(graph: &WikiGraph) -> Vec<Vec<String>> {
+    strongly_connected_components(graph)
+        .into_iter()
+        .filter(|component| {
+            component.len() > 1 || graph.get_outlinks(&component[0]).contains(&component[0])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{PageNode, WikiLink};
+    use std::path::PathBuf;
+
+    fn page(name: &str) -> PageNode {
+        PageNode::new(name.to_string(), PathBuf::from(format!("{}.md", name)))
+    }
+
+    #[test]
+    fn test_shortest_path_direct() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_page(page("B"));
+        graph.add_link("A", "B", WikiLink::new());
+
+        assert_eq!(
+            shortest_path(&graph, "A", "B"),
+            Some(vec!["A".to_string(), "B".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_shortest_path_none_when_unreachable() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_page(page("B"));
+
+        assert_eq!(shortest_path(&graph, "A", "B"), None);
+    }
+
+    #[test]
+    fn test_shortest_path_missing_page() {
+        let graph = WikiGraph::new();
+        assert_eq!(shortest_path(&graph, "A", "B"), None);
+    }
+
+    #[test]
+    fn test_shortest_path_same_page() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        assert_eq!(shortest_path(&graph, "A", "A"), Some(vec!["A".to_string()]));
+    }
+
+    #[test]
+    fn test_scc_singletons_when_acyclic() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_page(page("B"));
+        graph.add_link("A", "B", WikiLink::new());
+
+        let components = strongly_connected_components(&graph);
+        assert_eq!(components.len(), 2);
+        assert!(components.iter().all(|c| c.len() == 1));
+    }
+
+    #[test]
+    fn test_scc_groups_cycle() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_page(page("B"));
+        graph.add_link("A", "B", WikiLink::new());
+        graph.add_link("B", "A", WikiLink::new());
+
+        let components = strongly_connected_components(&graph);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), 2);
+    }
+
+    #[test]
+    fn test_is_cyclic_true_for_mutual_links() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_page(page("B"));
+        graph.add_link("A", "B", WikiLink::new());
+        graph.add_link("B", "A", WikiLink::new());
+
+        assert!(is_cyclic(&graph));
+    }
+
+    #[test]
+    fn test_is_cyclic_true_for_self_link() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_link("A", "A", WikiLink::new());
+
+        assert!(is_cyclic(&graph));
+    }
+
+    #[test]
+    fn test_is_cyclic_false_for_dag() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_page(page("B"));
+        graph.add_link("A", "B", WikiLink::new());
+
+        assert!(!is_cyclic(&graph));
+    }
+
+    #[test]
+    fn test_orphans_and_dead_ends_include_stubs() {
+        let mut graph = WikiGraph::new();
+        graph.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![crate::models::ParsedLink::new("Missing".to_string(), None)],
+            std::time::SystemTime::now(),
+        );
+
+        // "Missing" is a stub with no backing file, but it's still a real
+        // node for component analysis: it's an orphan-free dead end.
+        let orphans = orphans(&graph);
+        assert!(orphans.contains(&"Home".to_string()));
+        assert!(!orphans.contains(&"Missing".to_string()));
+
+        let dead_ends = dead_ends(&graph);
+        assert!(dead_ends.contains(&"Missing".to_string()));
+        assert!(!dead_ends.contains(&"Home".to_string()));
+    }
+
+    #[test]
+    fn test_orphan_pages_requires_no_backlinks_and_no_outlinks() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_page(page("B"));
+        graph.add_page(page("Isolated"));
+        graph.add_link("A", "B", WikiLink::new());
+
+        let orphans = orphan_pages(&graph);
+        assert_eq!(orphans, vec!["Isolated".to_string()]);
+    }
+
+    #[test]
+    fn test_connected_components_groups_across_link_direction() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_page(page("B"));
+        graph.add_page(page("C"));
+        graph.add_link("A", "B", WikiLink::new());
+
+        let mut components = connected_components(&graph);
+        for component in &mut components {
+            component.sort();
+        }
+        components.sort();
+
+        assert_eq!(
+            components,
+            vec![vec!["A".to_string(), "B".to_string()], vec!["C".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_find_cycles_reports_mutual_links_and_self_links() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_page(page("B"));
+        graph.add_page(page("C"));
+        graph.add_link("A", "B", WikiLink::new());
+        graph.add_link("B", "A", WikiLink::new());
+        graph.add_link("C", "C", WikiLink::new());
+
+        let mut cycles = find_cycles(&graph);
+        for cycle in &mut cycles {
+            cycle.sort();
+        }
+        cycles.sort();
+
+        assert_eq!(
+            cycles,
+            vec![vec!["A".to_string(), "B".to_string()], vec!["C".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_find_cycles_empty_for_dag() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("A"));
+        graph.add_page(page("B"));
+        graph.add_link("A", "B", WikiLink::new());
+
+        assert!(find_cycles(&graph).is_empty());
+    }
+}
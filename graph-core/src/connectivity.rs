@@ -0,0 +1,383 @@
+//! Incremental connectivity index over the undirected link graph.
+//!
+//! `update_page` runs on every edit and diffs outlinks into `LinkCreated`/
+//! `LinkRemoved` events; recomputing connectivity from scratch on each of
+//! those would mean re-walking the whole graph per keystroke. Instead,
+//! [`LinkCutForest`] maintains a forest of splay trees (a Sleator-Tarjan
+//! link-cut tree) over the graph's edges, answering [`LinkCutForest::connected`]
+//! in amortized O(log n) and staying in sync via [`LinkCutForest::link`] and
+//! [`LinkCutForest::cut`] as edges come and go.
+//!
+//! Edges are treated as undirected: a link from A to B and one from B to A
+//! represent the same connectivity edge. Before linking or cutting a
+//! specific pair, the first endpoint is re-rooted to its own tree (an
+//! "evert", via a lazily-propagated reversed-path flag) so the edge created
+//! or removed is exactly the one between the two given pages, not some
+//! other edge that happens to preserve connectivity at that instant — this
+//! matters once later cuts need to undo a specific earlier link.
+//!
+//! # Known limitation
+//! Per undirected edge, this tracks whether it was absorbed into the
+//! forest as a *tree edge* (the two endpoints were in different components
+//! at link time) or left as a redundant *non-tree edge* (they were already
+//! connected). Removing a non-tree edge is a no-op, since the forest never
+//! represented it — correct as long as some other path still connects the
+//! endpoints. Full fully-dynamic connectivity (replacing a cut tree edge by
+//! searching non-tree edges, a la Holm-de Lichtenberg-Thorup) is out of
+//! scope here; this index is best-effort and meant for the common case of
+//! a mostly-tree-shaped wiki link structure.
+
+use std::collections::{HashMap, HashSet};
+
+type NodeId = usize;
+
+struct Node {
+    parent: Option<NodeId>,
+    left: Option<NodeId>,
+    right: Option<NodeId>,
+    /// Lazily-propagated flag: this node's subtree represents its preferred
+    /// path in reverse order. Pushed down to children on access.
+    reversed: bool,
+}
+
+/// A link-cut forest over page names, answering undirected connectivity
+/// queries incrementally.
+#[derive(Default)]
+pub struct LinkCutForest {
+    nodes: Vec<Node>,
+    index: HashMap<String, NodeId>,
+    /// Canonicalized (sorted) pairs currently represented as a tree edge in
+    /// the forest, as opposed to a redundant edge that didn't need one.
+    tree_edges: HashSet<(String, String)>,
+}
+
+impl LinkCutForest {
+    /// Create an empty forest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn canonical_pair(a: &str, b: &str) -> (String, String) {
+        if a <= b {
+            (a.to_string(), b.to_string())
+        } else {
+            (b.to_string(), a.to_string())
+        }
+    }
+
+    /// Get or create the arena slot for `name`.
+    fn ensure_node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.index.get(name) {
+            return id;
+        }
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            parent: None,
+            left: None,
+            right: None,
+            reversed: false,
+        });
+        self.index.insert(name.to_string(), id);
+        id
+    }
+
+    fn is_root(&self, v: NodeId) -> bool {
+        match self.nodes[v].parent {
+            None => true,
+            Some(p) => self.nodes[p].left != Some(v) && self.nodes[p].right != Some(v),
+        }
+    }
+
+    /// Push this node's reversed flag down to its splay-tree children.
+    fn push_down(&mut self, v: NodeId) {
+        if !self.nodes[v].reversed {
+            return;
+        }
+        self.nodes[v].reversed = false;
+        let l = self.nodes[v].left;
+        let r = self.nodes[v].right;
+        self.nodes[v].left = r;
+        self.nodes[v].right = l;
+        if let Some(l) = l {
+            self.nodes[l].reversed ^= true;
+        }
+        if let Some(r) = r {
+            self.nodes[r].reversed ^= true;
+        }
+    }
+
+    /// Rotate `v` up past its splay-tree parent. Callers must have already
+    /// pushed down lazy flags along the root-to-`v` path.
+    fn rotate(&mut self, v: NodeId) {
+        let p = self.nodes[v].parent.expect("rotate requires a parent");
+        let g = self.nodes[p].parent;
+        let p_was_root = self.is_root(p);
+
+        if self.nodes[p].left == Some(v) {
+            let b = self.nodes[v].right;
+            self.nodes[p].left = b;
+            if let Some(b) = b {
+                self.nodes[b].parent = Some(p);
+            }
+            self.nodes[v].right = Some(p);
+        } else {
+            let b = self.nodes[v].left;
+            self.nodes[p].right = b;
+            if let Some(b) = b {
+                self.nodes[b].parent = Some(p);
+            }
+            self.nodes[v].left = Some(p);
+        }
+
+        self.nodes[p].parent = Some(v);
+        self.nodes[v].parent = g;
+
+        if !p_was_root {
+            let g = g.expect("non-root parent implies a grandparent");
+            if self.nodes[g].left == Some(p) {
+                self.nodes[g].left = Some(v);
+            } else if self.nodes[g].right == Some(p) {
+                self.nodes[g].right = Some(v);
+            }
+            // Otherwise `g` was only `p`'s path-parent pointer; `v` inherits
+            // that same virtual pointer via `self.nodes[v].parent = g` above.
+        }
+    }
+
+    /// Splay `v` to the root of its splay tree.
+    fn splay(&mut self, v: NodeId) {
+        // Push down lazy reversed flags from the real root to `v` first,
+        // top-down, so rotations below see correct left/right pointers.
+        let mut path = vec![v];
+        let mut cur = v;
+        while !self.is_root(cur) {
+            cur = self.nodes[cur].parent.unwrap();
+            path.push(cur);
+        }
+        for node in path.into_iter().rev() {
+            self.push_down(node);
+        }
+
+        while !self.is_root(v) {
+            let p = self.nodes[v].parent.unwrap();
+            if !self.is_root(p) {
+                let g = self.nodes[p].parent.unwrap();
+                let zig_zig = (self.nodes[g].left == Some(p)) == (self.nodes[p].left == Some(v));
+                if zig_zig {
+                    self.rotate(p);
+                } else {
+                    self.rotate(v);
+                }
+            }
+            self.rotate(v);
+        }
+    }
+
+    /// Bring the path from `v`'s represented-tree root to `v` together into
+    /// one splay tree, with `v` splayed to its root.
+    fn access(&mut self, v: NodeId) {
+        self.splay(v);
+        self.nodes[v].right = None;
+        while let Some(w) = self.nodes[v].parent {
+            self.splay(w);
+            self.nodes[w].right = Some(v);
+            self.splay(v);
+        }
+    }
+
+    /// Find the root of the represented tree containing `v`.
+    fn find_root(&mut self, v: NodeId) -> NodeId {
+        self.access(v);
+        let mut cur = v;
+        self.push_down(cur);
+        while let Some(l) = self.nodes[cur].left {
+            cur = l;
+            self.push_down(cur);
+        }
+        self.splay(cur);
+        cur
+    }
+
+    /// Make `v` the root of its represented tree (an "evert").
+    fn make_root(&mut self, v: NodeId) {
+        self.access(v);
+        self.nodes[v].reversed ^= true;
+    }
+
+    fn connected_nodes(&mut self, a: NodeId, b: NodeId) -> bool {
+        a == b || self.find_root(a) == self.find_root(b)
+    }
+
+    /// Record an undirected edge between `a` and `b`. If they're already in
+    /// the same represented tree, the edge is tracked as redundant rather
+    /// than added to the forest.
+    pub fn link(&mut self, a: &str, b: &str) {
+        if a == b {
+            return;
+        }
+        let key = Self::canonical_pair(a, b);
+        if self.tree_edges.contains(&key) {
+            return;
+        }
+
+        let va = self.ensure_node(a);
+        let vb = self.ensure_node(b);
+
+        if self.connected_nodes(va, vb) {
+            return;
+        }
+
+        self.make_root(va);
+        self.nodes[va].parent = Some(vb);
+        self.tree_edges.insert(key);
+    }
+
+    /// Remove an undirected edge between `a` and `b`. Only has an effect if
+    /// the edge was previously absorbed into the forest as a tree edge (see
+    /// the module-level known limitation for redundant edges).
+    pub fn cut(&mut self, a: &str, b: &str) {
+        let key = Self::canonical_pair(a, b);
+        if !self.tree_edges.remove(&key) {
+            return;
+        }
+
+        let (Some(&va), Some(&vb)) = (self.index.get(a), self.index.get(b)) else {
+            return;
+        };
+
+        self.make_root(va);
+        self.access(vb);
+
+        // `a` and `b` are directly adjacent iff, with `b` accessed, `a` is
+        // its whole left subtree and `a` has no right child (nothing
+        // between them on the path).
+        if self.nodes[vb].left == Some(va) && self.nodes[va].right.is_none() {
+            self.nodes[va].parent = None;
+            self.nodes[vb].left = None;
+        }
+    }
+
+    /// Whether `a` and `b` are in the same connected component.
+    ///
+    /// Registers either name as a fresh isolated node if it hasn't been
+    /// seen via [`LinkCutForest::link`] yet.
+    pub fn connected(&mut self, a: &str, b: &str) -> bool {
+        let va = self.ensure_node(a);
+        let vb = self.ensure_node(b);
+        self.connected_nodes(va, vb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlinked_pages_not_connected() {
+        let mut forest = LinkCutForest::new();
+        forest.link("A", "B");
+        assert!(!forest.connected("A", "C"));
+    }
+
+    #[test]
+    fn test_direct_link_is_connected() {
+        let mut forest = LinkCutForest::new();
+        forest.link("A", "B");
+        assert!(forest.connected("A", "B"));
+    }
+
+    #[test]
+    fn test_transitive_connectivity() {
+        let mut forest = LinkCutForest::new();
+        forest.link("A", "B");
+        forest.link("B", "C");
+        assert!(forest.connected("A", "C"));
+    }
+
+    #[test]
+    fn test_cut_disconnects_tree_edge() {
+        let mut forest = LinkCutForest::new();
+        forest.link("A", "B");
+        forest.link("B", "C");
+        forest.cut("A", "B");
+        assert!(!forest.connected("A", "C"));
+        assert!(forest.connected("B", "C"));
+    }
+
+    #[test]
+    fn test_cutting_redundant_edge_keeps_components_connected() {
+        let mut forest = LinkCutForest::new();
+        // A cycle: A-B, B-C, C-A. The last link is redundant (A and C are
+        // already connected via A-B-C) so it's never absorbed as a tree
+        // edge; cutting it should be a no-op.
+        forest.link("A", "B");
+        forest.link("B", "C");
+        forest.link("C", "A");
+        forest.cut("C", "A");
+        assert!(forest.connected("A", "C"));
+    }
+
+    #[test]
+    fn test_self_link_is_noop() {
+        let mut forest = LinkCutForest::new();
+        forest.link("A", "A");
+        assert!(forest.connected("A", "A"));
+    }
+
+    #[test]
+    fn test_reconnect_after_cut_via_new_edge() {
+        let mut forest = LinkCutForest::new();
+        forest.link("A", "B");
+        forest.cut("A", "B");
+        assert!(!forest.connected("A", "B"));
+        forest.link("A", "B");
+        assert!(forest.connected("A", "B"));
+    }
+
+    #[test]
+    fn test_star_topology_removing_one_spoke_keeps_other_spokes_connected() {
+        let mut forest = LinkCutForest::new();
+        forest.link("Hub", "A");
+        forest.link("Hub", "B");
+        forest.link("Hub", "C");
+        forest.cut("Hub", "B");
+
+        assert!(forest.connected("Hub", "A"));
+        assert!(forest.connected("Hub", "C"));
+        assert!(forest.connected("A", "C"));
+        assert!(!forest.connected("Hub", "B"));
+        assert!(!forest.connected("A", "B"));
+    }
+
+    #[test]
+    fn test_joining_two_multi_node_trees_at_non_root_endpoints() {
+        let mut forest = LinkCutForest::new();
+        // Two separate chains: X-Y and Z-W, neither Y nor Z is the other's
+        // tree root. Linking Y-Z must still correctly join the components,
+        // and cutting it later must precisely undo just that edge.
+        forest.link("X", "Y");
+        forest.link("Z", "W");
+        assert!(!forest.connected("Y", "Z"));
+
+        forest.link("Y", "Z");
+        assert!(forest.connected("X", "W"));
+
+        forest.cut("Y", "Z");
+        assert!(!forest.connected("X", "W"));
+        assert!(forest.connected("X", "Y"));
+        assert!(forest.connected("Z", "W"));
+    }
+
+    #[test]
+    fn test_cutting_one_of_several_edges_preserves_the_rest() {
+        let mut forest = LinkCutForest::new();
+        forest.link("A", "B");
+        forest.link("B", "C");
+        forest.link("C", "D");
+        forest.cut("B", "C");
+
+        assert!(forest.connected("A", "B"));
+        assert!(forest.connected("C", "D"));
+        assert!(!forest.connected("A", "D"));
+    }
+}
@@ -0,0 +1,173 @@
+//! Observed-remove set (OR-Set), the CRDT backing [`WikiGraph::merge`].
+//!
+//! Each page's outlink set is represented as an `OrSet<String>` rather than
+//! a plain `HashSet`, so that two offline copies of the wiki can reconcile
+//! their edits without a central authority: every add is tagged with a
+//! unique [`Dot`] (which replica made it, and that replica's logical
+//! clock), and a remove records the set of dots it observed for the
+//! removed element rather than just deleting it outright. An element is
+//! present iff at least one of its add-dots hasn't been covered by a
+//! later-observed remove. Concurrent add and remove of the same element
+//! converge to "present" — add-wins, which is the right default for a
+//! wiki: a link someone just added shouldn't vanish because another
+//! offline replica independently deleted an older version of it.
+//!
+//! [`WikiGraph::merge`]: crate::graph::WikiGraph::merge
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Identifies the replica (offline copy of the wiki) that produced a dot.
+/// Callers merging independent copies must assign each a distinct id —
+/// [`OrSet`] only guarantees convergence if dots are actually unique.
+pub type ReplicaId = u64;
+
+/// A unique tag for one add operation: the replica that performed it, and
+/// that replica's logical clock value at the time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Dot {
+    pub replica: ReplicaId,
+    pub counter: u64,
+}
+
+/// An observed-remove set of `T`.
+#[derive(Clone, Debug)]
+pub struct OrSet<T: Eq + Hash + Clone> {
+    /// Every dot that has ever added each element, including ones later
+    /// covered by a remove.
+    adds: HashMap<T, HashSet<Dot>>,
+    /// Every dot that has been observed-removed, across all elements.
+    removes: HashSet<Dot>,
+}
+
+impl<T: Eq + Hash + Clone> Default for OrSet<T> {
+    fn default() -> Self {
+        Self {
+            adds: HashMap::new(),
+            removes: HashSet::new(),
+        }
+    }
+}
+
+impl<T: Eq + Hash + Clone> OrSet<T> {
+    /// Create an empty OR-Set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an add of `elem`, tagged with `dot`.
+    pub fn add(&mut self, elem: T, dot: Dot) {
+        self.adds.entry(elem).or_default().insert(dot);
+    }
+
+    /// Remove `elem`, by observed-removing every add-dot currently on
+    /// record for it. A concurrent add from another replica that this
+    /// remove never observed carries a different dot, so it survives —
+    /// the element reappears once that add is merged in.
+    pub fn remove(&mut self, elem: &T) {
+        if let Some(dots) = self.adds.get(elem) {
+            self.removes.extend(dots.iter().copied());
+        }
+    }
+
+    /// Whether `elem` has an add-dot not covered by any remove.
+    pub fn contains(&self, elem: &T) -> bool {
+        self.adds
+            .get(elem)
+            .is_some_and(|dots| dots.iter().any(|d| !self.removes.contains(d)))
+    }
+
+    /// Every element with at least one surviving add-dot.
+    pub fn elements(&self) -> HashSet<T> {
+        self.adds
+            .iter()
+            .filter(|(elem, _)| self.contains(elem))
+            .map(|(elem, _)| elem.clone())
+            .collect()
+    }
+
+    /// Merge `other` into `self`: union the add-sets and remove-sets and
+    /// let presence fall out of that, per standard OR-Set semantics. Order-
+    /// and idempotent- and commutative, so this converges regardless of
+    /// merge order or repetition.
+    pub fn merge(&mut self, other: &OrSet<T>) {
+        for (elem, dots) in &other.adds {
+            self.adds.entry(elem.clone()).or_default().extend(dots);
+        }
+        self.removes.extend(other.removes.iter().copied());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dot(replica: ReplicaId, counter: u64) -> Dot {
+        Dot { replica, counter }
+    }
+
+    #[test]
+    fn test_add_then_contains() {
+        let mut set = OrSet::new();
+        set.add("a", dot(1, 0));
+        assert!(set.contains(&"a"));
+    }
+
+    #[test]
+    fn test_remove_observed_add() {
+        let mut set = OrSet::new();
+        set.add("a", dot(1, 0));
+        set.remove(&"a");
+        assert!(!set.contains(&"a"));
+    }
+
+    #[test]
+    fn test_concurrent_add_and_remove_is_add_wins() {
+        // Replica 1 adds "a", replica 2 independently (without observing
+        // replica 1's add) removes its own, unrelated copy of "a". After
+        // merging, replica 1's add-dot was never observed by the remove,
+        // so it survives.
+        let mut replica1 = OrSet::new();
+        replica1.add("a", dot(1, 0));
+
+        let mut replica2 = OrSet::new();
+        replica2.add("a", dot(2, 0));
+        replica2.remove(&"a");
+
+        replica1.merge(&replica2);
+        assert!(replica1.contains(&"a"));
+    }
+
+    #[test]
+    fn test_merge_is_commutative() {
+        let mut replica1 = OrSet::new();
+        replica1.add("a", dot(1, 0));
+        replica1.remove(&"a");
+
+        let mut replica2 = OrSet::new();
+        replica2.add("a", dot(1, 0));
+        replica2.add("b", dot(2, 0));
+
+        let mut merged_ab = replica1.clone();
+        merged_ab.merge(&replica2);
+
+        let mut merged_ba = replica2.clone();
+        merged_ba.merge(&replica1);
+
+        assert_eq!(merged_ab.elements(), merged_ba.elements());
+    }
+
+    #[test]
+    fn test_merge_is_idempotent() {
+        let mut replica1 = OrSet::new();
+        replica1.add("a", dot(1, 0));
+
+        let replica2 = replica1.clone();
+
+        replica1.merge(&replica2);
+        replica1.merge(&replica2);
+
+        assert_eq!(replica1.elements().len(), 1);
+        assert!(replica1.contains(&"a"));
+    }
+}
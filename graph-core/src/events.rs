@@ -23,11 +23,23 @@ pub enum GraphEvent {
     /// A page was deleted (file removed)
     PageDeleted { name: String },
 
+    /// A page was renamed or moved, its file identity (inode/device on
+    /// Unix, file index on Windows) matched across a debounced batch
+    /// rather than going through a PageDeleted + PageCreated pair. Its
+    /// outlinks and backlinks are preserved.
+    PageRenamed { old_name: String, new_name: String },
+
     /// A new link was added between pages
     LinkCreated { from: String, to: String },
 
     /// A link was removed between pages
     LinkRemoved { from: String, to: String },
+
+    /// A page's inbound link set changed: some other page started or
+    /// stopped linking to it. Fired alongside the corresponding
+    /// `LinkCreated`/`LinkRemoved` so a renderer knows to regenerate
+    /// `page`'s "What links here" section, including for stub targets.
+    BacklinkChanged { page: String },
 }
 
 #[pymethods]
@@ -35,25 +47,38 @@ impl GraphEvent {
     /// Get the event type as a string.
     ///
     /// Returns one of: "page_created", "page_updated", "page_deleted",
-    /// "link_created", "link_removed"
+    /// "page_renamed", "link_created", "link_removed", "backlink_changed"
     fn event_type(&self) -> &str {
         match self {
             GraphEvent::PageCreated { .. } => "page_created",
             GraphEvent::PageUpdated { .. } => "page_updated",
             GraphEvent::PageDeleted { .. } => "page_deleted",
+            GraphEvent::PageRenamed { .. } => "page_renamed",
             GraphEvent::LinkCreated { .. } => "link_created",
             GraphEvent::LinkRemoved { .. } => "link_removed",
+            GraphEvent::BacklinkChanged { .. } => "backlink_changed",
         }
     }
 
     /// Get the primary page name associated with the event.
     ///
-    /// Returns the page name for page events, None for link events.
+    /// Returns the page name for page events and `BacklinkChanged` (the
+    /// new name for `PageRenamed`), None for link events.
     fn page_name(&self) -> Option<String> {
         match self {
             GraphEvent::PageCreated { name } => Some(name.clone()),
             GraphEvent::PageUpdated { name } => Some(name.clone()),
             GraphEvent::PageDeleted { name } => Some(name.clone()),
+            GraphEvent::PageRenamed { new_name, .. } => Some(new_name.clone()),
+            GraphEvent::BacklinkChanged { page } => Some(page.clone()),
+            _ => None,
+        }
+    }
+
+    /// Get the previous name for a `PageRenamed` event, None otherwise.
+    fn renamed_from(&self) -> Option<String> {
+        match self {
+            GraphEvent::PageRenamed { old_name, .. } => Some(old_name.clone()),
             _ => None,
         }
     }
@@ -85,12 +110,18 @@ impl GraphEvent {
             GraphEvent::PageCreated { name } => format!("GraphEvent.PageCreated('{}')", name),
             GraphEvent::PageUpdated { name } => format!("GraphEvent.PageUpdated('{}')", name),
             GraphEvent::PageDeleted { name } => format!("GraphEvent.PageDeleted('{}')", name),
+            GraphEvent::PageRenamed { old_name, new_name } => {
+                format!("GraphEvent.PageRenamed('{}' -> '{}')", old_name, new_name)
+            }
             GraphEvent::LinkCreated { from, to } => {
                 format!("GraphEvent.LinkCreated('{}' -> '{}')", from, to)
             }
             GraphEvent::LinkRemoved { from, to } => {
                 format!("GraphEvent.LinkRemoved('{}' -> '{}')", from, to)
             }
+            GraphEvent::BacklinkChanged { page } => {
+                format!("GraphEvent.BacklinkChanged('{}')", page)
+            }
         }
     }
 }
@@ -250,4 +281,30 @@ mod tests {
         assert!(link_event.__repr__().contains("A"));
         assert!(link_event.__repr__().contains("B"));
     }
+
+    #[test]
+    fn test_page_renamed_event() {
+        let event = GraphEvent::PageRenamed {
+            old_name: "Old".to_string(),
+            new_name: "New".to_string(),
+        };
+        assert_eq!(event.event_type(), "page_renamed");
+        assert_eq!(event.page_name(), Some("New".to_string()));
+        assert_eq!(event.renamed_from(), Some("Old".to_string()));
+        assert!(event.__repr__().contains("Old"));
+        assert!(event.__repr__().contains("New"));
+    }
+
+    #[test]
+    fn test_backlink_changed_event() {
+        let event = GraphEvent::BacklinkChanged {
+            page: "Target".to_string(),
+        };
+        assert_eq!(event.event_type(), "backlink_changed");
+        assert_eq!(event.page_name(), Some("Target".to_string()));
+        assert_eq!(event.link_from(), None);
+        assert_eq!(event.link_to(), None);
+        assert!(event.__repr__().contains("BacklinkChanged"));
+        assert!(event.__repr__().contains("Target"));
+    }
 }
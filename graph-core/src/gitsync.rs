@@ -0,0 +1,151 @@
+//! Translating a git change log into graph updates.
+//!
+//! `git whatchanged` (and the `git diff --name-status` lines embedded in
+//! it) reports, per commit, which files were Added, Modified, Deleted, or
+//! Renamed between two revisions. [`parse_whatchanged`] picks just those
+//! status lines out of the raw output, and
+//! [`crate::graph::WikiGraph::apply_git_changes`] turns the resulting
+//! [`ChangedFile`]s into the minimal sequence of `update_page`/
+//! `remove_page` calls needed to catch the graph up to the new revision —
+//! re-parsing only the files that actually changed, rather than rescanning
+//! the whole tree the way [`crate::graph::WikiGraph::sync_from_directory`] does.
+
+use std::path::PathBuf;
+
+/// A single markdown file's status change between two git revisions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangedFile {
+    Added(PathBuf),
+    Modified(PathBuf),
+    Deleted(PathBuf),
+}
+
+fn is_markdown(path: &str) -> bool {
+    path.ends_with(".md")
+}
+
+/// Whether `status` actually has the shape of a git status code
+/// (`A`, `M`, `D`, or `R`/`C` followed by a similarity percentage, e.g.
+/// `R100`), rather than just happening to start with the right byte.
+/// Guards against misreading ordinary prose in a commit message (e.g. a
+/// line starting with "Modified ...") as a real status line.
+fn is_status_code(status: &str) -> bool {
+    let mut chars = status.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    let rest = chars.as_str();
+
+    match first {
+        'A' | 'M' | 'D' => rest.is_empty(),
+        'R' | 'C' => !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit()),
+        _ => false,
+    }
+}
+
+/// Parse `git whatchanged`/`git diff --name-status`-style output into a
+/// list of file changes, ignoring commit headers, diff stats, and anything
+/// else that isn't a recognized status line. Non-markdown files are
+/// dropped, since those are the only ones the graph tracks.
+///
+/// A rename or copy line (`R100\told\tnew`, `C100\told\tnew`) becomes a
+/// `Deleted(old)` + `Added(new)` pair.
+pub fn parse_whatchanged(output: &str) -> Vec<ChangedFile> {
+    let mut changes = Vec::new();
+
+    for line in output.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(status) = fields.next() else {
+            continue;
+        };
+        let rest: Vec<&str> = fields.collect();
+        if rest.is_empty() {
+            continue;
+        }
+
+        if !is_status_code(status) {
+            continue;
+        }
+
+        match status.as_bytes().first() {
+            Some(b'A') if is_markdown(rest[0]) => {
+                changes.push(ChangedFile::Added(PathBuf::from(rest[0])));
+            }
+            Some(b'M') if is_markdown(rest[0]) => {
+                changes.push(ChangedFile::Modified(PathBuf::from(rest[0])));
+            }
+            Some(b'D') if is_markdown(rest[0]) => {
+                changes.push(ChangedFile::Deleted(PathBuf::from(rest[0])));
+            }
+            Some(b'R') | Some(b'C') if rest.len() >= 2 => {
+                if is_markdown(rest[0]) {
+                    changes.push(ChangedFile::Deleted(PathBuf::from(rest[0])));
+                }
+                if is_markdown(rest[1]) {
+                    changes.push(ChangedFile::Added(PathBuf::from(rest[1])));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_added_modified_deleted() {
+        let output = "M\tintro.md\nA\tblog/new-post.md\nD\told.md\n";
+        assert_eq!(
+            parse_whatchanged(output),
+            vec![
+                ChangedFile::Modified(PathBuf::from("intro.md")),
+                ChangedFile::Added(PathBuf::from("blog/new-post.md")),
+                ChangedFile::Deleted(PathBuf::from("old.md")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ignores_non_markdown_files() {
+        let output = "M\tREADME\nA\timages/logo.png\n";
+        assert!(parse_whatchanged(output).is_empty());
+    }
+
+    #[test]
+    fn test_ignores_commit_headers_and_blank_lines() {
+        let output = "commit abc123\nAuthor: Jane <jane@example.com>\nDate:   Mon Jan 1\n\n    Commit message\n\nM\tintro.md\n";
+        assert_eq!(
+            parse_whatchanged(output),
+            vec![ChangedFile::Modified(PathBuf::from("intro.md"))]
+        );
+    }
+
+    #[test]
+    fn test_ignores_commit_message_prose_resembling_a_status_line() {
+        // First token "Modified" starts with 'M' and the next whitespace-
+        // separated token is a .md path, but "Modified" isn't a real git
+        // status code (M is never more than one character), so this must
+        // not be misread as a Modified(notes.md) change.
+        let output = "commit abc123\n\n    Modified notes.md yesterday\n\nA\tnew.md\n";
+        assert_eq!(
+            parse_whatchanged(output),
+            vec![ChangedFile::Added(PathBuf::from("new.md"))]
+        );
+    }
+
+    #[test]
+    fn test_rename_becomes_delete_and_add() {
+        let output = "R100\told-name.md\tnew-name.md\n";
+        assert_eq!(
+            parse_whatchanged(output),
+            vec![
+                ChangedFile::Deleted(PathBuf::from("old-name.md")),
+                ChangedFile::Added(PathBuf::from("new-name.md")),
+            ]
+        );
+    }
+}
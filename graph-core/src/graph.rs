@@ -4,18 +4,28 @@
 //! and their link relationships. It uses petgraph for efficient graph
 //! operations and provides methods for querying backlinks and outlinks.
 
+use crate::connectivity::LinkCutForest;
+use crate::crdt::{Dot, OrSet, ReplicaId};
 use crate::events::GraphEvent;
-use crate::models::{PageNode, ParsedLink, WikiLink};
+use crate::gitsync::ChangedFile;
+use crate::models::{LinkKind, PageNode, ParsedLink, WikiLink};
+use crate::pagespec::{build_page_tree, PageSpec, PageSpecError, PageTree};
 use crate::parser::parse_markdown;
-use crate::query::{matches_all_filters, Filter, MetaTableResult, MetaTableRow};
+use crate::profiling::{ProfileEntry, Profiler};
+use crate::query::{
+    collect_highlight_predicates, highlight_value, matches_all_filters, FacetBucket, FacetResult,
+    Filter, MetaTableResult, MetaTableRow,
+};
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
-use std::collections::{HashMap, HashSet};
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime};
 
 /// The wiki graph structure.
 ///
@@ -28,15 +38,173 @@ pub struct WikiGraph {
 
     /// Map from page name to node index for O(1) lookups.
     node_index: HashMap<String, NodeIndex>,
+
+    /// Last-seen modification time for each page backed by a real file on
+    /// disk, as of the last `build_from_directory`/`sync_from_directory`.
+    /// Used by `sync_from_directory` to tell real files apart from stub
+    /// nodes created for not-yet-existing link targets, and to detect
+    /// which files need reparsing.
+    known_files: HashMap<String, SystemTime>,
+
+    /// Per-file `(content hash, mtime)` snapshot as of the last
+    /// `rebuild_incremental`, keyed by path relative to the data
+    /// directory. mtime is only used as a cheap pre-filter: a file whose
+    /// mtime hasn't moved is assumed unchanged and is never rehashed; one
+    /// whose mtime did move is rehashed, and only actually reparsed if the
+    /// hash also changed.
+    content_hashes: HashMap<PathBuf, (String, SystemTime)>,
+
+    /// Incremental dynamic-connectivity index, kept in sync with link
+    /// changes once enabled via [`WikiGraph::enable_connectivity_index`].
+    /// `None` until then, so callers that never ask for it pay no cost.
+    connectivity: Option<LinkCutForest>,
+
+    /// This replica's id, used to tag every add this instance makes into
+    /// `link_sets` so that [`WikiGraph::merge`] with another replica's
+    /// copy converges correctly. See [`WikiGraph::new_with_replica`].
+    replica_id: ReplicaId,
+
+    /// This replica's logical clock, incremented on every outlink add.
+    clock: u64,
+
+    /// Each page's outlink set as an observed-remove CRDT, mirroring the
+    /// edges materialized into `graph` but carrying the add/remove dots
+    /// `merge` needs to reconcile with another replica. Keyed by source
+    /// page name.
+    link_sets: HashMap<String, OrSet<String>>,
+
+    /// Self-profiling timers, off by default. Wrapped in a `RefCell` so
+    /// that read-only methods like `query` can record timings without
+    /// becoming `&mut self`. See [`WikiGraph::enable_profiling`].
+    profiler: RefCell<Profiler>,
+}
+
+/// Summary of a [`WikiGraph::rebuild_incremental`] call: how many files
+/// were actually reparsed vs. skipped because their content hash (or, as a
+/// cheap pre-filter, their mtime) hadn't changed, and how many pages were
+/// removed for files that disappeared.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RebuildStats {
+    pub changed: usize,
+    pub skipped: usize,
+    pub removed: usize,
+}
+
+/// Compute a stable hex-encoded content hash for a file's raw bytes.
+fn hash_content(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Options controlling [`WikiGraph::to_dot_graph`] rendering.
+#[derive(Clone, Debug, Default)]
+pub struct DotOptions {
+    /// Restrict the render to pages reachable from this root page within
+    /// `max_hops` outlink hops. `None` renders the whole graph.
+    pub root: Option<String>,
+
+    /// Maximum hop distance from `root` to include. Ignored when `root`
+    /// is `None`.
+    pub max_hops: usize,
+
+    /// Whether to label edges with the link's display text
+    /// (`[[Target|text]]`), when present.
+    pub show_edge_labels: bool,
+}
+
+/// Output format for [`WikiGraph::export_graph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphExportFormat {
+    /// GraphViz DOT.
+    Dot,
+    /// GraphML (XML), readable by Gephi, yEd, and most graph databases.
+    GraphMl,
+    /// A minimal `{"nodes": [...], "edges": [...]}` JSON document.
+    Json,
+}
+
+/// Options controlling [`WikiGraph::export_graph`].
+#[derive(Clone, Debug, Default)]
+pub struct GraphExportOptions {
+    /// Metadata keys to embed as node attributes, for pages that have them.
+    pub metadata_keys: Vec<String>,
+
+    /// Whether to include edges that point at stub pages (link targets
+    /// with no backing file) and the stub nodes themselves. `false` drops
+    /// both.
+    pub include_dangling: bool,
 }
 
 impl WikiGraph {
-    /// Create a new empty WikiGraph.
+    /// Create a new empty WikiGraph, as the sole replica (id 0).
+    ///
+    /// Fine for a single local copy. Callers who intend to [`WikiGraph::merge`]
+    /// multiple independently-edited copies back together must construct
+    /// each with [`WikiGraph::new_with_replica`] and a distinct id instead —
+    /// merge only converges correctly if every replica's dots are unique.
     pub fn new() -> Self {
+        Self::new_with_replica(0)
+    }
+
+    /// Create a new empty WikiGraph identified as replica `replica_id`.
+    pub fn new_with_replica(replica_id: ReplicaId) -> Self {
         Self {
             graph: DiGraph::new(),
             node_index: HashMap::new(),
+            known_files: HashMap::new(),
+            content_hashes: HashMap::new(),
+            connectivity: None,
+            replica_id,
+            clock: 0,
+            link_sets: HashMap::new(),
+            profiler: RefCell::new(Profiler::new()),
+        }
+    }
+
+    /// Turn self-profiling on or off.
+    ///
+    /// While enabled, major operations (directory scan, markdown parse,
+    /// link resolution, query evaluation) record their call count and
+    /// duration; see [`WikiGraph::profile_snapshot`]. Disabling clears
+    /// whatever was accumulated, so overhead is near zero once off.
+    pub fn enable_profiling(&mut self, enabled: bool) {
+        self.profiler.borrow_mut().set_enabled(enabled);
+    }
+
+    /// Whether self-profiling is currently enabled.
+    pub fn is_profiling_enabled(&self) -> bool {
+        self.profiler.borrow().is_enabled()
+    }
+
+    /// Current profiling snapshot: one entry per named operation recorded
+    /// since profiling was last enabled.
+    pub fn profile_snapshot(&self) -> Vec<ProfileEntry> {
+        self.profiler.borrow().snapshot()
+    }
+
+    /// Turn on the incremental connectivity index, seeding it from every
+    /// edge currently in the graph.
+    ///
+    /// Once enabled, [`WikiGraph::update_page`] keeps the index in sync
+    /// incrementally rather than rebuilding it, so this only needs to be
+    /// called once (e.g. after the initial directory scan).
+    pub fn enable_connectivity_index(&mut self) {
+        let mut forest = LinkCutForest::new();
+        for edge in self.graph.edge_references() {
+            let from = self.graph[edge.source()].name.clone();
+            let to = self.graph[edge.target()].name.clone();
+            forest.link(&from, &to);
         }
+        self.connectivity = Some(forest);
+    }
+
+    /// Whether `a` and `b` are connected, ignoring link direction.
+    ///
+    /// Returns `None` if [`WikiGraph::enable_connectivity_index`] hasn't
+    /// been called.
+    pub fn connected(&mut self, a: &str, b: &str) -> Option<bool> {
+        self.connectivity.as_mut().map(|f| f.connected(a, b))
     }
 
     /// Add a page to the graph.
@@ -125,6 +293,12 @@ impl WikiGraph {
 
     /// Get backlinks for a page (pages that link TO this page).
     ///
+    /// This is backed by petgraph's own incoming-edge adjacency, which is
+    /// kept up to date automatically as edges are added and removed, so
+    /// there's no separate inverse index to maintain here. See
+    /// `GraphEvent::BacklinkChanged` for the corresponding change
+    /// notification.
+    ///
     /// # Arguments
     /// * `name` - The page name to find backlinks for
     ///
@@ -142,6 +316,27 @@ impl WikiGraph {
             .collect()
     }
 
+    /// Get backlinks for a page as full `PageNode`s, rather than just their
+    /// names (see [`WikiGraph::get_backlinks`]), so a "what links here?"
+    /// sidebar doesn't need a follow-up lookup per result.
+    ///
+    /// # Arguments
+    /// * `name` - The page name to find backlinks for
+    ///
+    /// # Returns
+    /// A vector of `PageNode`s that link to the specified page.
+    pub fn backlinks(&self, name: &str) -> Vec<&PageNode> {
+        let target_idx = match self.node_index.get(name) {
+            Some(&idx) => idx,
+            None => return Vec::new(),
+        };
+
+        self.graph
+            .neighbors_directed(target_idx, Direction::Incoming)
+            .map(|idx| &self.graph[idx])
+            .collect()
+    }
+
     /// Get outlinks for a page (pages that this page links TO).
     ///
     /// # Arguments
@@ -161,10 +356,116 @@ impl WikiGraph {
             .collect()
     }
 
+    /// The [`LinkKind`] of the edge from `from` to `to`, if one exists —
+    /// lets a caller tell a navigational `[[Page]]` link apart from an
+    /// `![[Page]]` transclusion between the same two pages.
+    pub fn link_kind(&self, from: &str, to: &str) -> Option<LinkKind> {
+        let from_idx = *self.node_index.get(from)?;
+        let to_idx = *self.node_index.get(to)?;
+        let edge_id = self.graph.find_edge(from_idx, to_idx)?;
+        Some(self.graph[edge_id].kind)
+    }
+
+    /// Like [`WikiGraph::get_outlinks`], but excluding
+    /// [`LinkKind::Transclusion`] edges — used internally for the
+    /// navigational-only bookkeeping (the CRDT outlink OR-set, link-change
+    /// events, the connectivity forest) that embeds don't participate in.
+    fn navigational_outlinks(&self, name: &str) -> HashSet<String> {
+        let source_idx = match self.node_index.get(name) {
+            Some(&idx) => idx,
+            None => return HashSet::new(),
+        };
+
+        self.graph
+            .edges_directed(source_idx, Direction::Outgoing)
+            .filter(|edge| edge.weight().kind == LinkKind::Navigational)
+            .map(|edge| self.graph[edge.target()].name.clone())
+            .collect()
+    }
+
+    /// Like [`WikiGraph::get_outlinks`], but excluding
+    /// [`LinkKind::Transclusion`] edges, so a page that only `![[Embeds]]`
+    /// another isn't reported as linking to it.
+    ///
+    /// This is the edge-kind-aware view the query layer
+    /// ([`crate::pagespec::PageSpec`], [`crate::query::Filter`]) uses, to
+    /// match the navigational/transclusion distinction
+    /// [`WikiGraph::update_page`]'s CRDT bookkeeping already enforces.
+    pub fn get_navigational_outlinks(&self, name: &str) -> Vec<String> {
+        self.navigational_outlinks(name).into_iter().collect()
+    }
+
+    /// Like [`WikiGraph::get_backlinks`], but excluding
+    /// [`LinkKind::Transclusion`] edges, so a page embedded via `![[...]]`
+    /// elsewhere (but never navigationally linked) isn't reported as having
+    /// a backlink there. See [`WikiGraph::get_navigational_outlinks`].
+    pub fn get_navigational_backlinks(&self, name: &str) -> Vec<String> {
+        let target_idx = match self.node_index.get(name) {
+            Some(&idx) => idx,
+            None => return Vec::new(),
+        };
+
+        self.graph
+            .edges_directed(target_idx, Direction::Incoming)
+            .filter(|edge| edge.weight().kind == LinkKind::Navigational)
+            .map(|edge| self.graph[edge.source()].name.clone())
+            .collect()
+    }
+
+    /// Find every link that points at a stub page (a link target with no
+    /// backing file on disk).
+    ///
+    /// Mirrors ikiwiki's `brokenlinks` directive: since stub nodes are
+    /// created transparently whenever a link's target doesn't yet exist
+    /// (see [`WikiGraph::link_to_target`]), this lets a caller surface
+    /// "wanted pages" without re-walking the filesystem.
+    ///
+    /// # Returns
+    /// `(source page, missing target)` pairs for every edge whose target
+    /// is a stub.
+    pub fn broken_links(&self) -> Vec<(String, String)> {
+        self.graph
+            .edge_references()
+            .filter_map(|edge| {
+                let target = &self.graph[edge.target()];
+                if target.is_stub {
+                    let source = &self.graph[edge.source()];
+                    Some((source.name.clone(), target.name.clone()))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Find every real page with no incoming links.
+    ///
+    /// Mirrors ikiwiki's `orphans` directive. Stub pages are excluded,
+    /// since an unreferenced stub isn't a real orphaned page.
+    ///
+    /// # Returns
+    /// References to every non-stub page with zero backlinks.
+    pub fn orphans(&self) -> Vec<&PageNode> {
+        self.graph
+            .node_indices()
+            .filter(|&idx| {
+                !self.graph[idx].is_stub
+                    && self
+                        .graph
+                        .neighbors_directed(idx, Direction::Incoming)
+                        .next()
+                        .is_none()
+            })
+            .map(|idx| &self.graph[idx])
+            .collect()
+    }
+
     /// Clear the graph, removing all pages and links.
     pub fn clear(&mut self) {
         self.graph.clear();
         self.node_index.clear();
+        self.known_files.clear();
+        self.content_hashes.clear();
     }
 
     /// Remove a page and all its edges from the graph.
@@ -180,6 +481,24 @@ impl WikiGraph {
     /// true if the page was removed, false if it didn't exist.
     pub fn remove_page(&mut self, name: &str) -> bool {
         if let Some(idx) = self.node_index.remove(name) {
+            self.link_sets.remove(name);
+
+            // Best-effort: sever this page's edges in the connectivity
+            // index before it disappears from the graph. The forest has no
+            // notion of node removal, so the page's node stays allocated
+            // in it (harmless, just unreachable from anywhere else) but
+            // must not keep neighbors falsely connected through it.
+            if let Some(forest) = self.connectivity.as_mut() {
+                let neighbors: Vec<String> = self
+                    .graph
+                    .neighbors_undirected(idx)
+                    .map(|n| self.graph[n].name.clone())
+                    .collect();
+                for neighbor in neighbors {
+                    forest.cut(name, &neighbor);
+                }
+            }
+
             // Get the index of the last node before removal
             let last_idx = NodeIndex::new(self.graph.node_count() - 1);
 
@@ -202,6 +521,63 @@ impl WikiGraph {
         }
     }
 
+    /// Remove a deleted file's page, demoting it to a stub instead of a
+    /// hard [`WikiGraph::remove_page`] if other pages still hold a live
+    /// link to it.
+    ///
+    /// Petgraph's node removal drops a node's edges along with it, so a
+    /// straight `remove_page` on a page with backlinks would silently
+    /// erase those now-dangling references instead of leaving them
+    /// visible to [`WikiGraph::broken_links`]. Demoting in place (via
+    /// [`WikiGraph::add_page`], which updates a node rather than
+    /// recreating it) keeps the incoming edges intact.
+    ///
+    /// Shared by every path that reacts to a file disappearing from disk:
+    /// [`WikiGraph::sync_from_directory`], [`WikiGraph::rebuild_incremental`],
+    /// and [`WikiGraph::apply_git_changes`].
+    ///
+    /// # Returns
+    /// `true` if the page existed and was removed or demoted, `false` if
+    /// there was no such page.
+    fn remove_or_demote_deleted_page(&mut self, name: &str) -> bool {
+        if self.backlinks(name).is_empty() {
+            self.remove_page(name)
+        } else if let Some(page) = self.get_page(name) {
+            let file_path = page.file_path.clone();
+            self.remove_outgoing_edges(name);
+            self.add_page(PageNode::stub(name.to_string(), file_path));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Rename a page in place, preserving its outgoing and incoming links.
+    ///
+    /// Petgraph edges are keyed by `NodeIndex`, which doesn't change here,
+    /// so every existing outlink/backlink keeps pointing at the same (now
+    /// renamed) node automatically — only the name-keyed side tables
+    /// (`node_index`, `link_sets`) need to move from `old_name` to
+    /// `new_name`. Used by the watcher's rename detection to avoid the
+    /// drop-and-recreate churn of a delete followed by a create.
+    ///
+    /// # Returns
+    /// true if `old_name` existed and was renamed, false otherwise.
+    pub fn rename_page(&mut self, old_name: &str, new_name: &str, new_path: PathBuf) -> bool {
+        let Some(idx) = self.node_index.remove(old_name) else {
+            return false;
+        };
+        self.node_index.insert(new_name.to_string(), idx);
+        self.graph[idx].name = new_name.to_string();
+        self.graph[idx].file_path = new_path;
+
+        if let Some(link_set) = self.link_sets.remove(old_name) {
+            self.link_sets.insert(new_name.to_string(), link_set);
+        }
+
+        true
+    }
+
     /// Remove all outgoing edges from a page.
     ///
     /// Used when updating a page's links to clear old links before adding new ones.
@@ -231,6 +607,11 @@ impl WikiGraph {
     /// 3. Adds new outgoing links
     /// 4. Returns events for link changes
     ///
+    /// Link targets are resolved with ikiwiki's "bestlink" algorithm (see
+    /// [`WikiGraph::resolve_bestlink`]) before being added, so e.g. a
+    /// `[[FAQ]]` link from `support/Intro` prefers an existing
+    /// `support/FAQ` over a top-level `FAQ`.
+    ///
     /// # Arguments
     /// * `name` - The page name
     /// * `file_path` - Relative path to the file
@@ -247,61 +628,358 @@ impl WikiGraph {
         metadata: HashMap<String, Vec<String>>,
         links: Vec<ParsedLink>,
         last_modified: SystemTime,
+    ) -> Vec<GraphEvent> {
+        self.update_page_impl(name, file_path, metadata, links, Vec::new(), last_modified, None)
+    }
+
+    /// Like [`WikiGraph::update_page`], but also stamps the resulting
+    /// `PageNode` with `content_hash` and threads `embeds` through as
+    /// [`LinkKind::Transclusion`] edges (see
+    /// [`WikiGraph::update_page_with_embeds`]). Used by
+    /// [`WikiGraph::rebuild_incremental`] so the hash it just computed to
+    /// decide whether to reparse is persisted on the node for next time.
+    pub fn update_page_with_hash(
+        &mut self,
+        name: &str,
+        file_path: PathBuf,
+        metadata: HashMap<String, Vec<String>>,
+        links: Vec<ParsedLink>,
+        embeds: Vec<ParsedLink>,
+        last_modified: SystemTime,
+        content_hash: String,
+    ) -> Vec<GraphEvent> {
+        self.update_page_impl(
+            name,
+            file_path,
+            metadata,
+            links,
+            embeds,
+            last_modified,
+            Some(content_hash),
+        )
+    }
+
+    /// Like [`WikiGraph::update_page`], but also threads `embeds` —
+    /// Obsidian-style `![[Page]]` transclusions, see
+    /// [`crate::parser::ParsedPage::embeds`] — through as edges tagged
+    /// [`LinkKind::Transclusion`], distinct from the [`LinkKind::Navigational`]
+    /// edges `links` produces. Unlike navigational links, embed edges
+    /// aren't reconciled through the CRDT outlink OR-set: they're simply
+    /// rebuilt from `embeds` on every call, like `outbound_links`.
+    pub fn update_page_with_embeds(
+        &mut self,
+        name: &str,
+        file_path: PathBuf,
+        metadata: HashMap<String, Vec<String>>,
+        links: Vec<ParsedLink>,
+        embeds: Vec<ParsedLink>,
+        last_modified: SystemTime,
+    ) -> Vec<GraphEvent> {
+        self.update_page_impl(name, file_path, metadata, links, embeds, last_modified, None)
+    }
+
+    fn update_page_impl(
+        &mut self,
+        name: &str,
+        file_path: PathBuf,
+        metadata: HashMap<String, Vec<String>>,
+        links: Vec<ParsedLink>,
+        embeds: Vec<ParsedLink>,
+        last_modified: SystemTime,
+        content_hash: Option<String>,
     ) -> Vec<GraphEvent> {
         let mut events = Vec::new();
 
-        // Get old outlinks before update
-        let old_outlinks: HashSet<String> = self.get_outlinks(name).into_iter().collect();
+        // Get old outlinks before update. Embed (transclusion) edges are
+        // deliberately excluded: they're not part of the CRDT outlink
+        // OR-set or the Link*/BacklinkChanged event stream, so mixing
+        // them into this diff would make an unchanged `![[...]]` embed
+        // look like a navigational link that was removed on every call.
+        let old_outlinks = self.navigational_outlinks(name);
 
         // Create/update the page node
-        let page = PageNode::with_metadata(name.to_string(), file_path, metadata, last_modified);
+        let mut page =
+            PageNode::with_metadata(name.to_string(), file_path, metadata, last_modified);
+        page.content_hash = content_hash;
+        page.outbound_links = links.clone();
         self.add_page(page);
 
         // Remove all existing outgoing edges
         self.remove_outgoing_edges(name);
 
-        // Add new links
+        // Add new links, resolved through bestlink
         let mut new_outlinks: HashSet<String> = HashSet::new();
 
         for link in &links {
-            // Ensure target page exists (create stub if needed)
-            if !self.page_exists(&link.target) {
-                let stub = PageNode::new(
-                    link.target.clone(),
-                    PathBuf::from(format!("{}.md", link.target)),
-                );
-                self.add_page(stub);
-            }
-
-            // Add the link
-            let wiki_link = match &link.display_text {
-                Some(text) => WikiLink::with_display_text(text.clone()),
-                None => WikiLink::new(),
-            };
-            self.add_link(name, &link.target, wiki_link);
-            new_outlinks.insert(link.target.clone());
+            let resolved = self.link_to_target(name, link);
+            new_outlinks.insert(resolved);
         }
 
         // Generate link change events
+        let replica_id = self.replica_id;
+        let link_set = self.link_sets.entry(name.to_string()).or_default();
+
+        // Links removed: in old but not in new
+        for target in old_outlinks.difference(&new_outlinks) {
+            link_set.remove(target);
+        }
+
+        // Links created: in new but not in old
+        for target in new_outlinks.difference(&old_outlinks) {
+            let dot = Dot {
+                replica: replica_id,
+                counter: self.clock,
+            };
+            self.clock += 1;
+            link_set.add(target.clone(), dot);
+        }
+
         // Links removed: in old but not in new
         for target in old_outlinks.difference(&new_outlinks) {
+            if let Some(forest) = self.connectivity.as_mut() {
+                forest.cut(name, target);
+            }
             events.push(GraphEvent::LinkRemoved {
                 from: name.to_string(),
                 to: target.clone(),
             });
+            events.push(GraphEvent::BacklinkChanged {
+                page: target.clone(),
+            });
         }
 
         // Links created: in new but not in old
         for target in new_outlinks.difference(&old_outlinks) {
+            if let Some(forest) = self.connectivity.as_mut() {
+                forest.link(name, target);
+            }
             events.push(GraphEvent::LinkCreated {
                 from: name.to_string(),
                 to: target.clone(),
             });
+            events.push(GraphEvent::BacklinkChanged {
+                page: target.clone(),
+            });
+        }
+
+        // Add embed (transclusion) edges, rebuilt fresh from `embeds` on
+        // every call, same as `outbound_links` — unlike navigational
+        // links, these aren't part of the CRDT outlink OR-set, so they
+        // don't produce Link*/BacklinkChanged events.
+        for embed in &embeds {
+            self.embed_to_target(name, embed);
+        }
+
+        events
+    }
+
+    /// Merge `other`'s edits into `self`, converging regardless of merge
+    /// order — see the [`crate::crdt`] module for the OR-Set this is
+    /// built on. Each page's outlink set is reconciled as an OR-Set: a
+    /// link concurrently added on one replica and removed (from an older
+    /// observation) on another resolves to present, add-wins. Page
+    /// content and metadata use last-writer-wins by `last_modified`.
+    ///
+    /// Returns the net `LinkCreated`/`LinkRemoved` events so callers can
+    /// react to a merge the same way they react to a local edit (e.g. to
+    /// know which pages need re-rendering).
+    ///
+    /// `self` and `other` must have been constructed with distinct replica
+    /// ids (see [`WikiGraph::new_with_replica`]) for dots to stay unique
+    /// and the merge to converge correctly. Only outlinks established
+    /// through `update_page` are tracked in the OR-Set and reconciled here
+    /// — edges added directly via [`WikiGraph::add_link`] bypass the CRDT
+    /// bookkeeping entirely, so a page built that way should be treated as
+    /// read-only with respect to merging.
+    pub fn merge(&mut self, other: &WikiGraph) -> Vec<GraphEvent> {
+        let mut events = Vec::new();
+
+        // Page content/metadata: last-writer-wins by last_modified — except
+        // a stub never beats real content, regardless of timestamps. Stubs
+        // are stamped with `SystemTime::now()` at creation (see
+        // `PageNode::stub`), so comparing timestamps alone would let a
+        // stub freshly created for a dangling link overwrite a genuine,
+        // older page from the other replica.
+        for page in other.list_pages() {
+            let keep_ours = match self.get_page(&page.name) {
+                None => false,
+                Some(existing) if existing.is_stub && !page.is_stub => false,
+                Some(existing) if !existing.is_stub && page.is_stub => true,
+                Some(existing) => existing.last_modified >= page.last_modified,
+            };
+            if !keep_ours {
+                self.add_page(page.clone());
+            }
+        }
+
+        // Outlink sets: union each page's OR-Set with ours, then
+        // materialize whatever net presence change that produces.
+        for (name, other_set) in &other.link_sets {
+            let old_presence: HashSet<String> = self.get_outlinks(name).into_iter().collect();
+
+            let link_set = self.link_sets.entry(name.clone()).or_default();
+            link_set.merge(other_set);
+            let new_presence = link_set.elements();
+
+            for target in old_presence.difference(&new_presence) {
+                self.remove_edge(name, target);
+                if let Some(forest) = self.connectivity.as_mut() {
+                    forest.cut(name, target);
+                }
+                events.push(GraphEvent::LinkRemoved {
+                    from: name.clone(),
+                    to: target.clone(),
+                });
+                events.push(GraphEvent::BacklinkChanged {
+                    page: target.clone(),
+                });
+            }
+
+            for target in new_presence.difference(&old_presence) {
+                self.ensure_stub(target);
+                self.add_link(name, target, WikiLink::new());
+                if let Some(forest) = self.connectivity.as_mut() {
+                    forest.link(name, target);
+                }
+                events.push(GraphEvent::LinkCreated {
+                    from: name.clone(),
+                    to: target.clone(),
+                });
+                events.push(GraphEvent::BacklinkChanged {
+                    page: target.clone(),
+                });
+            }
         }
 
         events
     }
 
+    /// Create a stub page for `name` if no page by that name exists yet.
+    fn ensure_stub(&mut self, name: &str) {
+        if !self.page_exists(name) {
+            let stub = PageNode::stub(name.to_string(), PathBuf::from(format!("{}.md", name)));
+            self.add_page(stub);
+        }
+    }
+
+    /// Remove the single edge from `from` to `to`, if present.
+    fn remove_edge(&mut self, from: &str, to: &str) {
+        if let Some(&from_idx) = self.node_index.get(from) {
+            if let Some(&to_idx) = self.node_index.get(to) {
+                if let Some(edge_id) = self.graph.find_edge(from_idx, to_idx) {
+                    self.graph.remove_edge(edge_id);
+                }
+            }
+        }
+    }
+
+    /// Resolve a link target using ikiwiki's "bestlink" algorithm.
+    ///
+    /// To resolve link text `target` from a page named `from` (e.g.
+    /// `a/b/c`), tries, most-local first, treating each prefix of `from`'s
+    /// own path as a namespace: `a/b/c/target`, `a/b/target`, `a/target`,
+    /// then bare `target` at the root. Returns the first candidate that
+    /// already exists in the graph, or — if none do — `target` as a
+    /// sibling of `from` (`a/b/target` for `a/b/c`, bare `target` for a
+    /// root-level `from`), so a stub can be created there.
+    ///
+    /// An absolute target (leading `/`) bypasses the search entirely and
+    /// resolves from the root.
+    ///
+    /// A bare `#Section` link (empty `target`, used for intra-page anchors)
+    /// resolves to `from` itself, rather than falling through to an empty
+    /// page name.
+    fn resolve_bestlink(&self, from: &str, target: &str) -> String {
+        if target.is_empty() {
+            return from.to_string();
+        }
+
+        if let Some(stripped) = target.strip_prefix('/') {
+            return stripped.to_string();
+        }
+
+        let segments: Vec<&str> = from.split('/').collect();
+
+        for end in (0..segments.len()).rev() {
+            let candidate = format!("{}/{}", segments[..=end].join("/"), target);
+            if self.page_exists(&candidate) {
+                return candidate;
+            }
+        }
+
+        if self.page_exists(target) {
+            return target.to_string();
+        }
+
+        if segments.len() > 1 {
+            format!("{}/{}", segments[..segments.len() - 1].join("/"), target)
+        } else {
+            target.to_string()
+        }
+    }
+
+    /// Resolve `link`'s target from `source_page` via
+    /// [`WikiGraph::resolve_bestlink`], without mutating the graph — unlike
+    /// [`WikiGraph::link_to_target`], this doesn't create a stub or add an
+    /// edge, so it's safe for read-only callers like a link checker or a
+    /// theme deciding how to render a `[[...]]` reference.
+    pub fn resolve_link(&self, source_page: &PageNode, link: &ParsedLink) -> String {
+        self.resolve_bestlink(&source_page.name, &link.target)
+    }
+
+    /// Resolve `link` from `source_page` like [`WikiGraph::resolve_link`],
+    /// and render it as an href: the resolved page name, with `link`'s
+    /// `#anchor` appended if it has one. A bare `[[#Section]]` (empty
+    /// `target`) resolves to `source_page` itself via
+    /// [`WikiGraph::resolve_bestlink`], e.g. `Page#Section`, so intra-page
+    /// links don't point at a different, empty-named page.
+    pub fn resolve_link_href(&self, source_page: &PageNode, link: &ParsedLink) -> String {
+        let resolved = self.resolve_link(source_page, link);
+
+        match &link.anchor {
+            Some(anchor) => format!("{}#{}", resolved, anchor),
+            None => resolved,
+        }
+    }
+
+    /// Resolve `link`'s target from page `from` via [`WikiGraph::resolve_bestlink`],
+    /// creating a stub page at the resolved location if it doesn't exist
+    /// yet, then add the edge. Returns the resolved target name.
+    fn link_to_target(&mut self, from: &str, link: &ParsedLink) -> String {
+        let resolved = self.resolve_bestlink(from, &link.target);
+
+        if !self.page_exists(&resolved) {
+            let stub = PageNode::stub(resolved.clone(), PathBuf::from(format!("{}.md", resolved)));
+            self.add_page(stub);
+        }
+
+        let wiki_link = match &link.display_text {
+            Some(text) => WikiLink::with_display_text(text.clone()),
+            None => WikiLink::new(),
+        };
+        self.add_link(from, &resolved, wiki_link);
+
+        resolved
+    }
+
+    /// Resolve `embed`'s target from page `from` via
+    /// [`WikiGraph::resolve_bestlink`], creating a stub if it doesn't
+    /// exist yet, then add a [`LinkKind::Transclusion`] edge — like
+    /// [`WikiGraph::link_to_target`], but tagging the edge as an embed
+    /// rather than a navigational link.
+    fn embed_to_target(&mut self, from: &str, embed: &ParsedLink) -> String {
+        let resolved = self.resolve_bestlink(from, &embed.target);
+
+        if !self.page_exists(&resolved) {
+            let stub = PageNode::stub(resolved.clone(), PathBuf::from(format!("{}.md", resolved)));
+            self.add_page(stub);
+        }
+
+        self.add_link(from, &resolved, WikiLink::transclusion(embed.display_text.clone()));
+
+        resolved
+    }
+
     /// Query pages that match all filters.
     ///
     /// Returns references to all PageNodes that match every filter
@@ -313,40 +991,161 @@ impl WikiGraph {
     /// # Returns
     /// A vector of references to matching PageNodes.
     pub fn query(&self, filters: &[Filter]) -> Vec<&PageNode> {
-        self.graph
+        let start = Instant::now();
+        let matches: Vec<&PageNode> = self
+            .graph
             .node_weights()
             .filter(|page| matches_all_filters(page, filters, self))
-            .collect()
+            .collect();
+        self.profiler.borrow_mut().record("query", start.elapsed());
+        matches
     }
 
-    /// MetaTable query: filter pages and select specific columns.
+    /// Query pages using a [`PageSpec`] expression string.
     ///
-    /// Returns a structured result with rows containing only the
-    /// requested metadata columns.
+    /// This is the string-driven counterpart to [`WikiGraph::query`]'s
+    /// `Filter`-based AND-only matching: it supports glob name patterns,
+    /// `link()`/`backlink()`/`tagged()` selectors, and boolean combinators.
     ///
     /// # Arguments
-    /// * `filters` - Slice of filters to apply
-    /// * `columns` - Column names to include in results
+    /// * `spec` - A PageSpec expression, e.g. `"blog/* and not tagged(draft)"`
     ///
     /// # Returns
-    /// A MetaTableResult containing the matching rows with selected columns.
+    /// The matching pages, or an error if `spec` fails to parse.
+    pub fn query_pagespec(&self, spec: &str) -> Result<Vec<&PageNode>, PageSpecError> {
+        let parsed = PageSpec::parse(spec)?;
+        Ok(self
+            .graph
+            .node_weights()
+            .filter(|page| parsed.matches(page, self))
+            .collect())
+    }
+
+    /// Select the names of all pages matching an already-parsed [`PageSpec`].
     ///
-    /// # Special columns
-    /// - `name` - The page name (always available)
-    /// - `file_path` - The file path (always available)
-    /// - Any metadata key from frontmatter
-    pub fn metatable(&self, filters: &[Filter], columns: &[String]) -> MetaTableResult {
-        let matching_pages = self.query(filters);
+    /// Unlike [`WikiGraph::query_pagespec`], which parses its input and
+    /// returns full `&PageNode` references, this takes a pre-parsed spec
+    /// and returns just the matching page names, convenient for driving
+    /// reports and dynamic index pages.
+    pub fn select(&self, spec: &PageSpec) -> Vec<String> {
+        self.graph
+            .node_weights()
+            .filter(|page| spec.matches(page, self))
+            .map(|page| page.name.clone())
+            .collect()
+    }
 
-        let rows: Vec<MetaTableRow> = matching_pages
-            .iter()
-            .map(|page| {
-                let values: HashMap<String, Vec<String>> = columns
-                    .iter()
-                    .filter_map(|col| {
-                        if col == "name" {
-                            Some((col.clone(), vec![page.name.clone()]))
-                        } else if col == "file_path" {
+    /// Build a hierarchical page tree from a [`PageSpec`], reproducing
+    /// ikiwiki/riki's `map` directive.
+    ///
+    /// Collects all matching page names, splits each on `/`, and builds a
+    /// forest of nested nodes suitable for rendering a sitemap/outline.
+    /// When a matching page like `a/b/c` has no matching ancestor `a/b`,
+    /// an intermediate node is synthesized (`PageTree::is_real == false`)
+    /// so the tree stays connected. Sibling order is alphabetical.
+    ///
+    /// # Arguments
+    /// * `spec` - The PageSpec selecting which pages to include
+    pub fn map(&self, spec: &PageSpec) -> Vec<PageTree> {
+        let names: Vec<String> = self
+            .graph
+            .node_weights()
+            .filter(|page| spec.matches(page, self))
+            .map(|page| page.name.clone())
+            .collect();
+
+        build_page_tree(names)
+    }
+
+    /// Aggregate a multi-valued metadata field across pages matching a
+    /// [`PageSpec`], for rendering a tag cloud (riki's `pagestats`
+    /// directive).
+    ///
+    /// Counts how many matched pages carry each value of `key` (pages
+    /// lacking `key` entirely are skipped), then buckets each value's count
+    /// into one of 10 logarithmic weight classes `0..=9` for font-size-style
+    /// rendering: `floor((ln(count) - ln(min)) / (ln(max) - ln(min)) * 9)`.
+    /// When every value has the same count, all values get the middle
+    /// bucket (`5`), except when that shared count is `1`, which gets `0`.
+    ///
+    /// # Arguments
+    /// * `spec` - The PageSpec selecting which pages to include
+    /// * `key` - The metadata field to aggregate (e.g. `"tags"`)
+    ///
+    /// # Returns
+    /// `(value, count, bucket)` tuples, sorted alphabetically by value.
+    pub fn pagestats(&self, spec: &PageSpec, key: &str) -> Vec<(String, usize, u8)> {
+        const BUCKETS: f64 = 10.0;
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for page in self.graph.node_weights() {
+            if !spec.matches(page, self) {
+                continue;
+            }
+            if let Some(values) = page.metadata.get(key) {
+                for value in values {
+                    *counts.entry(value.clone()).or_insert(0) += 1;
+                }
+            }
+        }
+
+        if counts.is_empty() {
+            return Vec::new();
+        }
+
+        let min = *counts.values().min().unwrap();
+        let max = *counts.values().max().unwrap();
+
+        let mut result: Vec<(String, usize, u8)> = counts
+            .into_iter()
+            .map(|(value, count)| {
+                let bucket = if max == min {
+                    if max == 1 {
+                        0
+                    } else {
+                        (BUCKETS / 2.0) as u8
+                    }
+                } else {
+                    let frac = ((count as f64).ln() - (min as f64).ln())
+                        / ((max as f64).ln() - (min as f64).ln());
+                    (frac * (BUCKETS - 1.0)).floor() as u8
+                };
+                (value, count, bucket)
+            })
+            .collect();
+
+        result.sort_by(|a, b| a.0.cmp(&b.0));
+        result
+    }
+
+    /// MetaTable query: filter pages and select specific columns.
+    ///
+    /// Returns a structured result with rows containing only the
+    /// requested metadata columns.
+    ///
+    /// # Arguments
+    /// * `filters` - Slice of filters to apply
+    /// * `columns` - Column names to include in results
+    ///
+    /// # Returns
+    /// A MetaTableResult containing the matching rows with selected columns.
+    ///
+    /// # Special columns
+    /// - `name` - The page name (always available)
+    /// - `file_path` - The file path (always available)
+    /// - Any metadata key from frontmatter
+    pub fn metatable(&self, filters: &[Filter], columns: &[String]) -> MetaTableResult {
+        let matching_pages = self.query(filters);
+
+        let rows: Vec<MetaTableRow> = matching_pages
+            .iter()
+            .map(|page| {
+                let values: HashMap<String, Vec<String>> = columns
+                    .iter()
+                    .filter_map(|col| {
+                        if col == "name" {
+                            Some((col.clone(), vec![page.name.clone()]))
+                        } else if col == "file_path" {
                             Some((
                                 col.clone(),
                                 vec![page.file_path.to_string_lossy().to_string()],
@@ -360,6 +1159,10 @@ impl WikiGraph {
                 MetaTableRow {
                     page_name: page.name.clone(),
                     values,
+                    distance: None,
+                    path_count: None,
+                    highlights: HashMap::new(),
+                    highlighted_values: HashMap::new(),
                 }
             })
             .collect();
@@ -370,426 +1173,2978 @@ impl WikiGraph {
         }
     }
 
-    /// Build the graph from a directory of markdown files.
+    /// Aggregate a single metadata key across every page: for each
+    /// distinct value, how many pages carry it and which ones.
     ///
-    /// Scans the directory for .md files, parses each one to extract
-    /// metadata and links, and builds the graph.
+    /// Multi-value fields (e.g. `tags: [a, b, c]`) are exploded, so a page
+    /// with three tags contributes one count to each of three buckets.
+    /// Pages that don't have `key` at all contribute nothing. Buckets are
+    /// sorted alphabetically by value.
     ///
     /// # Arguments
-    /// * `dir` - The directory path to scan
+    /// * `key` - The metadata key to aggregate (e.g. `"tags"`, `"status"`)
+    pub fn facet(&self, key: &str) -> FacetResult {
+        let mut buckets: HashMap<String, Vec<String>> = HashMap::new();
+
+        for page in self.list_pages() {
+            let Some(values) = page.metadata.get(key) else {
+                continue;
+            };
+            for value in values {
+                buckets
+                    .entry(value.clone())
+                    .or_default()
+                    .push(page.name.clone());
+            }
+        }
+
+        let mut buckets: Vec<FacetBucket> = buckets
+            .into_iter()
+            .map(|(value, pages)| FacetBucket {
+                value,
+                count: pages.len(),
+                pages,
+            })
+            .collect();
+        buckets.sort_by(|a, b| a.value.cmp(&b.value));
+
+        FacetResult {
+            key: key.to_string(),
+            buckets,
+        }
+    }
+
+    /// Run [`WikiGraph::facet`] over several metadata keys at once.
+    ///
+    /// # Arguments
+    /// * `keys` - The metadata keys to aggregate
     ///
     /// # Returns
-    /// Result indicating success or an IO error.
-    pub fn build_from_directory(&mut self, dir: &Path) -> io::Result<()> {
-        self.clear();
-
-        // Collect all markdown files and their parsed data
-        let mut parsed_pages: Vec<(String, PathBuf, ParsedPageData)> = Vec::new();
+    /// One `FacetResult` per key, in the same order as `keys`.
+    pub fn facet_counts(&self, keys: &[String]) -> Vec<FacetResult> {
+        keys.iter().map(|key| self.facet(key)).collect()
+    }
 
-        self.scan_directory(dir, dir, &mut parsed_pages)?;
+    /// MetaTable query ranked by link-distance proximity to a seed page.
+    ///
+    /// Behaves like [`WikiGraph::metatable`], but sorts the resulting rows
+    /// by shortest undirected link-path distance to `seed` (out- and
+    /// back-links both count as edges). Pages unreachable from `seed` sort
+    /// last. Each row's `distance` field is populated; when `k > 1`, each
+    /// row's `path_count` field is populated with the number of distinct
+    /// near-shortest paths found via [`WikiGraph::k_shortest_paths`].
+    ///
+    /// # Arguments
+    /// * `filters` - Slice of filters to apply
+    /// * `columns` - Column names to include in results
+    /// * `seed` - The page to rank proximity against
+    /// * `k` - Number of alternative paths to compute per row (1 disables path counting)
+    pub fn metatable_ranked(
+        &self,
+        filters: &[Filter],
+        columns: &[String],
+        seed: &str,
+        k: usize,
+    ) -> MetaTableResult {
+        let mut result = self.metatable(filters, columns);
+        let distances = self.undirected_distances_from(seed);
 
-        // First pass: add all pages as nodes
-        for (name, file_path, data) in &parsed_pages {
-            let node = PageNode::with_metadata(
-                name.clone(),
-                file_path.clone(),
-                data.metadata.clone(),
-                data.last_modified,
-            );
-            self.add_page(node);
+        for row in result.rows.iter_mut() {
+            row.distance = distances.get(&row.page_name).copied();
+            if k > 1 {
+                row.path_count = Some(self.k_shortest_paths(seed, &row.page_name, k).len());
+            }
         }
 
-        // Second pass: add links
-        // We need to handle links to pages that might not exist (create stub nodes)
-        for (name, _, data) in &parsed_pages {
-            for link in &data.links {
-                // Ensure target page exists (create stub if needed)
-                if !self.page_exists(&link.target) {
-                    // Create a stub node for the missing page
-                    let stub = PageNode::new(
-                        link.target.clone(),
-                        PathBuf::from(format!("{}.md", link.target)),
-                    );
-                    self.add_page(stub);
+        result
+            .rows
+            .sort_by_key(|row| row.distance.unwrap_or(usize::MAX));
+
+        result
+    }
+
+    /// MetaTable query with match highlighting.
+    ///
+    /// Behaves like [`WikiGraph::metatable`], but for each selected column
+    /// that was also matched by a `Contains`/`Matches` filter (including
+    /// inside `And`/`Or`/`Not` combinators), wraps the matched span in
+    /// `open`/`close` delimiters and records the matched byte range. Read
+    /// the results via `MetaTableRow::get_highlighted`/`highlights`.
+    ///
+    /// # Arguments
+    /// * `filters` - Slice of filters to apply
+    /// * `columns` - Column names to include in results
+    /// * `open` - Delimiter inserted before a matched span (default `<mark>`)
+    /// * `close` - Delimiter inserted after a matched span (default `</mark>`)
+    pub fn metatable_highlighted(
+        &self,
+        filters: &[Filter],
+        columns: &[String],
+        open: &str,
+        close: &str,
+    ) -> MetaTableResult {
+        let mut result = self.metatable(filters, columns);
+        let combined = Filter::And(filters.to_vec());
+
+        for row in result.rows.iter_mut() {
+            for column in columns {
+                let mut predicates = Vec::new();
+                collect_highlight_predicates(&combined, column, &mut predicates);
+                if predicates.is_empty() {
+                    continue;
                 }
 
-                // Add the link
-                let wiki_link = match &link.display_text {
-                    Some(text) => WikiLink::with_display_text(text.clone()),
-                    None => WikiLink::new(),
+                let values = match row.values.get(column) {
+                    Some(v) => v.clone(),
+                    None => continue,
                 };
-                self.add_link(name, &link.target, wiki_link);
+
+                let mut highlighted_values = Vec::with_capacity(values.len());
+                let mut spans = Vec::new();
+
+                for value in &values {
+                    match highlight_value(value, &predicates, open, close) {
+                        Some((highlighted, span)) => {
+                            spans.push(span);
+                            highlighted_values.push(highlighted);
+                        }
+                        None => highlighted_values.push(value.clone()),
+                    }
+                }
+
+                if !spans.is_empty() {
+                    row.highlights.insert(column.clone(), spans);
+                    row.highlighted_values.insert(column.clone(), highlighted_values);
+                }
             }
         }
 
-        Ok(())
+        result
     }
 
-    /// Recursively scan a directory for markdown files.
-    fn scan_directory(
-        &self,
-        base_dir: &Path,
-        current_dir: &Path,
-        results: &mut Vec<(String, PathBuf, ParsedPageData)>,
-    ) -> io::Result<()> {
-        if !current_dir.is_dir() {
-            return Ok(());
+    /// Compute shortest-path distances from `seed` to every reachable page,
+    /// via BFS over the undirected view of the link graph (out- and
+    /// back-links both count as edges).
+    pub fn undirected_distances_from(&self, seed: &str) -> HashMap<String, usize> {
+        let mut distances = HashMap::new();
+        if !self.page_exists(seed) {
+            return distances;
         }
 
-        for entry in fs::read_dir(current_dir)? {
-            let entry = entry?;
-            let path = entry.path();
+        let mut visited: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<(String, usize)> = VecDeque::new();
+        visited.insert(seed.to_string());
+        queue.push_back((seed.to_string(), 0));
 
-            if path.is_dir() {
-                self.scan_directory(base_dir, &path, results)?;
-            } else if path.extension().map_or(false, |ext| ext == "md") {
-                // Get the relative path from base_dir
-                let relative_path = path.strip_prefix(base_dir).unwrap_or(&path).to_path_buf();
+        while let Some((name, depth)) = queue.pop_front() {
+            distances.insert(name.clone(), depth);
 
-                // Derive page name from filename (without .md extension)
-                let name = path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("unknown")
-                    .to_string();
+            let mut neighbors = self.get_outlinks(&name);
+            neighbors.extend(self.get_backlinks(&name));
 
-                // Get file modification time
-                let last_modified = entry
-                    .metadata()
-                    .and_then(|m| m.modified())
-                    .unwrap_or_else(|_| SystemTime::now());
+            for neighbor in neighbors {
+                if visited.insert(neighbor.clone()) {
+                    queue.push_back((neighbor, depth + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Find up to `k` shortest/near-shortest distinct paths between two
+    /// pages using Yen's algorithm, with BFS as the underlying
+    /// shortest-path subroutine over the undirected link graph.
+    pub fn k_shortest_paths(&self, from: &str, to: &str, k: usize) -> Vec<Vec<String>> {
+        if k == 0 || !self.page_exists(from) || !self.page_exists(to) {
+            return Vec::new();
+        }
+
+        let first = match self.undirected_shortest_path(from, to, &HashSet::new(), &HashSet::new())
+        {
+            Some(path) => path,
+            None => return Vec::new(),
+        };
+
+        let mut paths: Vec<Vec<String>> = vec![first];
+        let mut candidates: Vec<Vec<String>> = Vec::new();
 
-                // Read and parse the file
-                match fs::read_to_string(&path) {
-                    Ok(content) => {
-                        let parsed = parse_markdown(&content);
-                        results.push((
-                            name,
-                            relative_path,
-                            ParsedPageData {
-                                metadata: parsed.metadata,
-                                links: parsed.links,
-                                last_modified,
-                            },
-                        ));
+        while paths.len() < k {
+            let prev_path = paths.last().unwrap().clone();
+
+            for i in 0..prev_path.len().saturating_sub(1) {
+                let spur_node = &prev_path[i];
+                let root_path = &prev_path[..=i];
+
+                // Remove edges used by the root path in any previously found path.
+                let mut excluded_edges: HashSet<(String, String)> = HashSet::new();
+                for path in &paths {
+                    if path.len() > i && path[..=i] == *root_path {
+                        excluded_edges.insert((path[i].clone(), path[i + 1].clone()));
                     }
-                    Err(e) => {
-                        eprintln!("Warning: Failed to read {}: {}", path.display(), e);
+                }
+
+                // Remove the root path's interior nodes to keep the spur path disjoint.
+                let excluded_nodes: HashSet<String> = root_path[..i].iter().cloned().collect();
+
+                if let Some(spur_path) =
+                    self.undirected_shortest_path(spur_node, to, &excluded_edges, &excluded_nodes)
+                {
+                    let mut total_path = root_path[..i].to_vec();
+                    total_path.extend(spur_path);
+                    if !paths.contains(&total_path) && !candidates.contains(&total_path) {
+                        candidates.push(total_path);
                     }
                 }
             }
+
+            if candidates.is_empty() {
+                break;
+            }
+
+            candidates.sort_by_key(|p| p.len());
+            paths.push(candidates.remove(0));
         }
 
-        Ok(())
+        paths
     }
-}
 
-impl Default for WikiGraph {
-    fn default() -> Self {
-        Self::new()
+    /// BFS shortest path over the undirected link graph, excluding the
+    /// given edges and nodes (used by [`WikiGraph::k_shortest_paths`] to
+    /// force deviations from previously found paths).
+    fn undirected_shortest_path(
+        &self,
+        from: &str,
+        to: &str,
+        excluded_edges: &HashSet<(String, String)>,
+        excluded_nodes: &HashSet<String>,
+    ) -> Option<Vec<String>> {
+        if from == to {
+            return Some(vec![from.to_string()]);
+        }
+
+        let mut visited: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<String> = VecDeque::new();
+        let mut prev: HashMap<String, String> = HashMap::new();
+
+        visited.insert(from.to_string());
+        queue.push_back(from.to_string());
+
+        while let Some(name) = queue.pop_front() {
+            let mut neighbors = self.get_outlinks(&name);
+            neighbors.extend(self.get_backlinks(&name));
+
+            for neighbor in neighbors {
+                if excluded_nodes.contains(&neighbor) || visited.contains(&neighbor) {
+                    continue;
+                }
+                if excluded_edges.contains(&(name.clone(), neighbor.clone()))
+                    || excluded_edges.contains(&(neighbor.clone(), name.clone()))
+                {
+                    continue;
+                }
+
+                visited.insert(neighbor.clone());
+                prev.insert(neighbor.clone(), name.clone());
+
+                if neighbor == to {
+                    let mut path = vec![neighbor.clone()];
+                    let mut cur = neighbor;
+                    while let Some(p) = prev.get(&cur) {
+                        path.push(p.clone());
+                        cur = p.clone();
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+
+                queue.push_back(neighbor);
+            }
+        }
+
+        None
     }
-}
 
-/// Internal struct to hold parsed page data during directory scan.
-struct ParsedPageData {
-    metadata: HashMap<String, Vec<String>>,
-    links: Vec<ParsedLink>,
-    last_modified: SystemTime,
-}
+    /// Render a query result as a GraphViz DOT document.
+    ///
+    /// Emits one node per matched page (labeled with the page name and the
+    /// selected `MetaTableResult` columns) and one edge per link between two
+    /// matched pages. When `include_neighbors` is true, pages one outlink
+    /// hop away from a matched page are also included, drawn with a dashed
+    /// style to distinguish them from the matched set.
+    ///
+    /// # Arguments
+    /// * `result` - The query result to render
+    /// * `include_neighbors` - Whether to include one hop of unmatched neighbors
+    ///
+    /// # Returns
+    /// A GraphViz DOT document as a string.
+    pub fn to_dot(&self, result: &MetaTableResult, include_neighbors: bool) -> String {
+        let matched: HashSet<String> = result.rows.iter().map(|r| r.page_name.clone()).collect();
+        let mut neighbors: HashSet<String> = HashSet::new();
+        let mut edges: Vec<(String, String)> = Vec::new();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        for name in &matched {
+            for target in self.get_outlinks(name) {
+                if matched.contains(&target) {
+                    edges.push((name.clone(), target));
+                } else if include_neighbors {
+                    neighbors.insert(target.clone());
+                    edges.push((name.clone(), target));
+                }
+            }
+        }
 
-    #[test]
-    fn test_new_graph() {
-        let graph = WikiGraph::new();
-        assert_eq!(graph.page_count(), 0);
-        assert_eq!(graph.link_count(), 0);
+        let mut dot = String::from("digraph wiki {\n");
+
+        for row in &result.rows {
+            let label = dot_node_label(&row.page_name, &row.values, &result.columns);
+            dot.push_str(&format!(
+                "  \"{}\" [label=\"{}\"];\n",
+                escape_dot(&row.page_name),
+                label
+            ));
+        }
+
+        for name in &neighbors {
+            dot.push_str(&format!(
+                "  \"{}\" [label=\"{}\", style=dashed];\n",
+                escape_dot(name),
+                escape_dot(name)
+            ));
+        }
+
+        for (from, to) in &edges {
+            dot.push_str(&format!(
+                "  \"{}\" -> \"{}\";\n",
+                escape_dot(from),
+                escape_dot(to)
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render the whole page-link graph (or a subgraph reachable from a
+    /// root page) as a GraphViz DOT document.
+    ///
+    /// Unlike [`WikiGraph::to_dot`], which renders a `MetaTable` query
+    /// result, this renders the graph itself: every page is a node and
+    /// every link is an edge, with no query involved. Stub pages (link
+    /// targets with no backing file, see [`PageNode::is_stub`]) are drawn
+    /// with a dashed style to distinguish them from real pages.
+    ///
+    /// # Arguments
+    /// * `opts` - Rendering options; see [`DotOptions`]
+    ///
+    /// # Returns
+    /// A GraphViz DOT document as a string.
+    pub fn to_dot_graph(&self, opts: &DotOptions) -> String {
+        let included = opts
+            .root
+            .as_ref()
+            .map(|root| self.reachable_within_hops(root, opts.max_hops));
+
+        let mut dot = String::from("digraph wiki {\n");
+
+        for node in self.graph.node_weights() {
+            if let Some(set) = &included {
+                if !set.contains(&node.name) {
+                    continue;
+                }
+            }
+
+            let style = if node.is_stub { ", style=dashed" } else { "" };
+            dot.push_str(&format!(
+                "  \"{}\" [label=\"{}\"{}];\n",
+                escape_dot(&node.name),
+                escape_dot(&node.name),
+                style
+            ));
+        }
+
+        for edge in self.graph.edge_references() {
+            let from = &self.graph[edge.source()].name;
+            let to = &self.graph[edge.target()].name;
+
+            if let Some(set) = &included {
+                if !set.contains(from) || !set.contains(to) {
+                    continue;
+                }
+            }
+
+            let label_attr = match opts.show_edge_labels.then(|| edge.weight().display_text.as_ref()).flatten() {
+                Some(text) => format!(" [label=\"{}\"]", escape_dot(text)),
+                None => String::new(),
+            };
+
+            dot.push_str(&format!(
+                "  \"{}\" -> \"{}\"{};\n",
+                escape_dot(from),
+                escape_dot(to),
+                label_attr
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Export the whole link graph in `format`, for use by external
+    /// visualization tools or graph databases, instead of reading it back
+    /// page by page.
+    ///
+    /// # Arguments
+    /// * `format` - The serialization format; see [`GraphExportFormat`]
+    /// * `opts` - Which metadata to embed and whether to include dangling
+    ///   links; see [`GraphExportOptions`]
+    ///
+    /// # Returns
+    /// The serialized graph as a string.
+    pub fn export_graph(&self, format: GraphExportFormat, opts: &GraphExportOptions) -> String {
+        match format {
+            GraphExportFormat::Dot => self.export_dot(opts),
+            GraphExportFormat::GraphMl => self.export_graphml(opts),
+            GraphExportFormat::Json => self.export_json(opts),
+        }
+    }
+
+    /// Nodes and edges to include for [`WikiGraph::export_graph`], with
+    /// stub nodes and their dangling edges dropped unless
+    /// `opts.include_dangling` is set.
+    fn export_nodes_and_edges(&self, opts: &GraphExportOptions) -> (Vec<&PageNode>, Vec<(&str, &str)>) {
+        let nodes: Vec<&PageNode> = self
+            .graph
+            .node_weights()
+            .filter(|node| opts.include_dangling || !node.is_stub)
+            .collect();
+        let node_names: HashSet<&str> = nodes.iter().map(|node| node.name.as_str()).collect();
+
+        let edges: Vec<(&str, &str)> = self
+            .graph
+            .edge_references()
+            .map(|edge| {
+                (
+                    self.graph[edge.source()].name.as_str(),
+                    self.graph[edge.target()].name.as_str(),
+                )
+            })
+            .filter(|(from, to)| node_names.contains(from) && node_names.contains(to))
+            .collect();
+
+        (nodes, edges)
+    }
+
+    fn export_dot(&self, opts: &GraphExportOptions) -> String {
+        let (nodes, edges) = self.export_nodes_and_edges(opts);
+        let mut dot = String::from("digraph wiki {\n");
+
+        for node in &nodes {
+            let mut attrs = format!("label=\"{}\"", escape_dot(&node.name));
+            if node.is_stub {
+                attrs.push_str(", style=dashed");
+            }
+            for key in &opts.metadata_keys {
+                if let Some(values) = node.metadata.get(key) {
+                    attrs.push_str(&format!(
+                        ", {}=\"{}\"",
+                        escape_dot(key),
+                        escape_dot(&values.join(","))
+                    ));
+                }
+            }
+            dot.push_str(&format!("  \"{}\" [{}];\n", escape_dot(&node.name), attrs));
+        }
+
+        for (from, to) in &edges {
+            dot.push_str(&format!(
+                "  \"{}\" -> \"{}\";\n",
+                escape_dot(from),
+                escape_dot(to)
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn export_graphml(&self, opts: &GraphExportOptions) -> String {
+        let (nodes, edges) = self.export_nodes_and_edges(opts);
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str("  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n");
+        for key in &opts.metadata_keys {
+            out.push_str(&format!(
+                "  <key id=\"{}\" for=\"node\" attr.name=\"{}\" attr.type=\"string\"/>\n",
+                escape_xml(key),
+                escape_xml(key)
+            ));
+        }
+        out.push_str("  <graph id=\"wiki\" edgedefault=\"directed\">\n");
+
+        for node in &nodes {
+            out.push_str(&format!("    <node id=\"{}\">\n", escape_xml(&node.name)));
+            out.push_str(&format!(
+                "      <data key=\"name\">{}</data>\n",
+                escape_xml(&node.name)
+            ));
+            for key in &opts.metadata_keys {
+                if let Some(values) = node.metadata.get(key) {
+                    out.push_str(&format!(
+                        "      <data key=\"{}\">{}</data>\n",
+                        escape_xml(key),
+                        escape_xml(&values.join(","))
+                    ));
+                }
+            }
+            out.push_str("    </node>\n");
+        }
+
+        for (from, to) in &edges {
+            out.push_str(&format!(
+                "    <edge source=\"{}\" target=\"{}\"/>\n",
+                escape_xml(from),
+                escape_xml(to)
+            ));
+        }
+
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    fn export_json(&self, opts: &GraphExportOptions) -> String {
+        let (nodes, edges) = self.export_nodes_and_edges(opts);
+
+        let mut out = String::from("{\n  \"nodes\": [\n");
+        for (i, node) in nodes.iter().enumerate() {
+            out.push_str(&format!(
+                "    {{\"name\": \"{}\", \"is_stub\": {}",
+                escape_json(&node.name),
+                node.is_stub
+            ));
+            for key in &opts.metadata_keys {
+                if let Some(values) = node.metadata.get(key) {
+                    let values_json = values
+                        .iter()
+                        .map(|v| format!("\"{}\"", escape_json(v)))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    out.push_str(&format!(", \"{}\": [{}]", escape_json(key), values_json));
+                }
+            }
+            out.push('}');
+            if i + 1 < nodes.len() {
+                out.push(',');
+            }
+            out.push('\n');
+        }
+        out.push_str("  ],\n  \"edges\": [\n");
+        for (i, (from, to)) in edges.iter().enumerate() {
+            out.push_str(&format!(
+                "    {{\"from\": \"{}\", \"to\": \"{}\"}}",
+                escape_json(from),
+                escape_json(to)
+            ));
+            if i + 1 < edges.len() {
+                out.push(',');
+            }
+            out.push('\n');
+        }
+        out.push_str("  ]\n}\n");
+        out
+    }
+
+    /// Collect the names of every page reachable from `root` by following
+    /// outlinks within `max_hops` hops (root included, at hop 0).
+    fn reachable_within_hops(&self, root: &str, max_hops: usize) -> HashSet<String> {
+        let mut visited: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<(String, usize)> = VecDeque::new();
+
+        if !self.page_exists(root) {
+            return visited;
+        }
+
+        visited.insert(root.to_string());
+        queue.push_back((root.to_string(), 0));
+
+        while let Some((name, depth)) = queue.pop_front() {
+            if depth >= max_hops {
+                continue;
+            }
+            for neighbor in self.get_outlinks(&name) {
+                if visited.insert(neighbor.clone()) {
+                    queue.push_back((neighbor, depth + 1));
+                }
+            }
+        }
+
+        visited
+    }
+
+    /// Build the graph from a directory of markdown files.
+    ///
+    /// Scans the directory for .md files, parses each one to extract
+    /// metadata and links, and builds the graph.
+    ///
+    /// # Arguments
+    /// * `dir` - The directory path to scan
+    ///
+    /// # Returns
+    /// Result indicating success or an IO error.
+    pub fn build_from_directory(&mut self, dir: &Path) -> io::Result<()> {
+        self.clear();
+
+        // Collect all markdown files and their parsed data
+        let mut parsed_pages: Vec<(String, PathBuf, ParsedPageData)> = Vec::new();
+
+        let scan_start = Instant::now();
+        scan_directory(dir, dir, &mut parsed_pages, &self.profiler)?;
+        self.profiler.borrow_mut().record("scan", scan_start.elapsed());
+
+        // First pass: add all pages as nodes
+        for (name, file_path, data) in &parsed_pages {
+            let mut node = PageNode::with_metadata(
+                name.clone(),
+                file_path.clone(),
+                data.metadata.clone(),
+                data.last_modified,
+            );
+            node.assets = data.assets.clone();
+            node.outbound_links = data.links.clone();
+            self.add_page(node);
+            self.known_files.insert(name.clone(), data.last_modified);
+        }
+
+        // Second pass: add links and embeds, resolved through bestlink
+        // (creates stub nodes for targets that don't match an existing
+        // page). Embeds are added as a distinct LinkKind::Transclusion
+        // edge so a renderer can tell "links to" from "embeds" apart.
+        let link_start = Instant::now();
+        for (name, _, data) in &parsed_pages {
+            for link in &data.links {
+                self.link_to_target(name, link);
+            }
+            for embed in &data.embeds {
+                self.embed_to_target(name, embed);
+            }
+        }
+        self.profiler
+            .borrow_mut()
+            .record("link_resolution", link_start.elapsed());
+
+        Ok(())
+    }
+
+    /// Incrementally update the graph from a directory of markdown files.
+    ///
+    /// Unlike [`WikiGraph::build_from_directory`], this does not clear the
+    /// graph and reparse everything. It scans the tree, compares each
+    /// file's modification time against `known_files`, and only reparses
+    /// pages that are new or whose mtime advanced. Pages whose backing
+    /// file has disappeared are removed via [`WikiGraph::remove_page`] —
+    /// unless another page still links to them, in which case they're
+    /// demoted to a stub in place instead, so the now-dangling reference
+    /// stays visible to [`WikiGraph::broken_links`] rather than having its
+    /// edge silently dropped along with the node. Every change routes
+    /// through [`WikiGraph::update_page`] so link-diff events are
+    /// produced, in addition to `PageCreated`/`PageUpdated`/`PageDeleted`
+    /// events for the pages themselves.
+    ///
+    /// After a sync, the graph matches what a fresh `build_from_directory`
+    /// would have produced: stubs are created for new link targets, and
+    /// stubs no longer referenced by any link are removed.
+    ///
+    /// # Arguments
+    /// * `dir` - The directory path to scan
+    ///
+    /// # Returns
+    /// The GraphEvents produced by the sync, in the order they occurred.
+    pub fn sync_from_directory(&mut self, dir: &Path) -> io::Result<Vec<GraphEvent>> {
+        let mut events = Vec::new();
+
+        let mut found: Vec<(String, PathBuf, SystemTime)> = Vec::new();
+        self.scan_directory_metadata(dir, dir, &mut found)?;
+        let found: HashMap<String, (PathBuf, SystemTime)> = found
+            .into_iter()
+            .map(|(name, path, mtime)| (name, (path, mtime)))
+            .collect();
+
+        // Files that were known to be real but no longer exist on disk.
+        let removed_names: Vec<String> = self
+            .known_files
+            .keys()
+            .filter(|name| !found.contains_key(*name))
+            .cloned()
+            .collect();
+
+        for name in removed_names {
+            self.known_files.remove(&name);
+            if self.remove_or_demote_deleted_page(&name) {
+                events.push(GraphEvent::PageDeleted { name });
+            }
+        }
+
+        // New or modified files.
+        for (name, (relative_path, mtime)) in &found {
+            let is_new = !self.known_files.contains_key(name);
+            let changed = match self.known_files.get(name) {
+                Some(known_mtime) => mtime > known_mtime,
+                None => true,
+            };
+
+            if !changed {
+                continue;
+            }
+
+            let full_path = dir.join(relative_path);
+            let content = fs::read_to_string(&full_path)?;
+            let parsed = parse_markdown(&content);
+
+            events.push(if is_new {
+                GraphEvent::PageCreated { name: name.clone() }
+            } else {
+                GraphEvent::PageUpdated { name: name.clone() }
+            });
+
+            let link_events = self.update_page_with_embeds(
+                name,
+                relative_path.clone(),
+                parsed.metadata,
+                parsed.links,
+                parsed.embeds,
+                *mtime,
+            );
+            events.extend(link_events);
+
+            self.known_files.insert(name.clone(), *mtime);
+        }
+
+        // Drop stubs that no link points at any more; a fresh
+        // build_from_directory would never have created them.
+        let unreferenced_stubs: Vec<String> = self
+            .graph
+            .node_indices()
+            .filter(|&idx| {
+                self.graph[idx].is_stub
+                    && self.graph.neighbors_directed(idx, Direction::Incoming).next().is_none()
+            })
+            .map(|idx| self.graph[idx].name.clone())
+            .collect();
+        for name in unreferenced_stubs {
+            self.remove_page(&name);
+        }
+
+        Ok(events)
+    }
+
+    /// Incrementally rebuild the graph using a content-hash snapshot
+    /// instead of mtimes, so a file touched without being changed (or
+    /// whose mtime isn't trustworthy, e.g. after a checkout) doesn't get
+    /// needlessly reparsed.
+    ///
+    /// mtime is still consulted first as a cheap pre-filter: a file whose
+    /// `content_hashes` entry has an unchanged mtime is assumed unchanged
+    /// and skipped without reading it. Only a file that's new, or whose
+    /// mtime moved, gets hashed; of those, only the ones whose hash
+    /// actually differs get reparsed through [`WikiGraph::update_page_with_hash`].
+    /// Files that disappeared are removed or demoted to a stub the same
+    /// way as [`WikiGraph::sync_from_directory`] — see
+    /// [`WikiGraph::remove_or_demote_deleted_page`].
+    ///
+    /// # Arguments
+    /// * `dir` - The directory path to scan
+    ///
+    /// # Returns
+    /// The GraphEvents produced, and a [`RebuildStats`] summarizing how
+    /// many files were reparsed, skipped, and removed.
+    pub fn rebuild_incremental(&mut self, dir: &Path) -> io::Result<(Vec<GraphEvent>, RebuildStats)> {
+        let rebuild_start = Instant::now();
+        let mut events = Vec::new();
+        let mut stats = RebuildStats::default();
+
+        let mut found: Vec<(String, PathBuf, SystemTime)> = Vec::new();
+        self.scan_directory_metadata(dir, dir, &mut found)?;
+        let found_paths: HashSet<PathBuf> =
+            found.iter().map(|(_, path, _)| path.clone()).collect();
+
+        // Files whose snapshot entry no longer matches anything on disk.
+        let removed_paths: Vec<PathBuf> = self
+            .content_hashes
+            .keys()
+            .filter(|path| !found_paths.contains(*path))
+            .cloned()
+            .collect();
+
+        for path in removed_paths {
+            self.content_hashes.remove(&path);
+            let name = name_from_relative_path(&path);
+            self.known_files.remove(&name);
+            if self.remove_or_demote_deleted_page(&name) {
+                events.push(GraphEvent::PageDeleted { name });
+                stats.removed += 1;
+            }
+        }
+
+        for (name, relative_path, mtime) in &found {
+            let mtime_unchanged = self
+                .content_hashes
+                .get(relative_path)
+                .map(|(_, known_mtime)| known_mtime == mtime)
+                .unwrap_or(false);
+
+            if mtime_unchanged {
+                stats.skipped += 1;
+                continue;
+            }
+
+            let full_path = dir.join(relative_path);
+            let content = fs::read_to_string(&full_path)?;
+            let hash = hash_content(content.as_bytes());
+
+            let hash_unchanged = self
+                .content_hashes
+                .get(relative_path)
+                .map(|(known_hash, _)| known_hash == &hash)
+                .unwrap_or(false);
+
+            if hash_unchanged {
+                // mtime moved but the bytes didn't: refresh the snapshot's
+                // mtime so the next call skips this file without rehashing,
+                // but don't reparse or emit any events for it.
+                self.content_hashes
+                    .insert(relative_path.clone(), (hash, *mtime));
+                stats.skipped += 1;
+                continue;
+            }
+
+            let is_new = !self.page_exists(name);
+            let parsed = parse_markdown(&content);
+
+            events.push(if is_new {
+                GraphEvent::PageCreated { name: name.clone() }
+            } else {
+                GraphEvent::PageUpdated { name: name.clone() }
+            });
+
+            let link_events = self.update_page_with_hash(
+                name,
+                relative_path.clone(),
+                parsed.metadata,
+                parsed.links,
+                parsed.embeds,
+                *mtime,
+                hash.clone(),
+            );
+            events.extend(link_events);
+
+            self.known_files.insert(name.clone(), *mtime);
+            self.content_hashes
+                .insert(relative_path.clone(), (hash, *mtime));
+            stats.changed += 1;
+        }
+
+        self.profiler.borrow_mut().record_items(
+            "rebuild",
+            rebuild_start.elapsed(),
+            stats.changed as u64,
+        );
+
+        Ok((events, stats))
+    }
+
+    /// Apply a git-derived set of file changes, re-parsing only the files
+    /// [`crate::gitsync::parse_whatchanged`] reports as Added/Modified and
+    /// removing pages for ones reported Deleted — rather than rescanning
+    /// the whole directory like [`WikiGraph::sync_from_directory`] does.
+    ///
+    /// Paths are relative to `dir`, matching what git reports. Every
+    /// change routes through [`WikiGraph::update_page`] and
+    /// [`WikiGraph::remove_or_demote_deleted_page`] (demoting to a stub
+    /// instead of a hard removal when the page still has backlinks) so
+    /// the same `PageCreated`/`PageUpdated`/`PageDeleted` and
+    /// `LinkCreated`/`LinkRemoved` events come out as a full sync would
+    /// produce for the same files.
+    ///
+    /// # Arguments
+    /// * `dir` - The directory the changed paths are relative to
+    /// * `changes` - The file changes to apply, in order
+    ///
+    /// # Returns
+    /// The GraphEvents produced, in the order they occurred.
+    pub fn apply_git_changes(
+        &mut self,
+        dir: &Path,
+        changes: &[ChangedFile],
+    ) -> io::Result<Vec<GraphEvent>> {
+        let mut events = Vec::new();
+
+        for change in changes {
+            match change {
+                ChangedFile::Deleted(path) => {
+                    let name = name_from_relative_path(path);
+                    self.known_files.remove(&name);
+                    if self.remove_or_demote_deleted_page(&name) {
+                        events.push(GraphEvent::PageDeleted { name });
+                    }
+                }
+                ChangedFile::Added(path) | ChangedFile::Modified(path) => {
+                    let name = name_from_relative_path(path);
+                    let is_new = !self.page_exists(&name);
+
+                    let full_path = dir.join(path);
+                    let content = fs::read_to_string(&full_path)?;
+                    let parsed = parse_markdown(&content);
+                    let mtime = fs::metadata(&full_path)?.modified()?;
+
+                    events.push(if is_new {
+                        GraphEvent::PageCreated { name: name.clone() }
+                    } else {
+                        GraphEvent::PageUpdated { name: name.clone() }
+                    });
+
+                    let link_events = self.update_page_with_embeds(
+                        &name,
+                        path.clone(),
+                        parsed.metadata,
+                        parsed.links,
+                        parsed.embeds,
+                        mtime,
+                    );
+                    events.extend(link_events);
+
+                    self.known_files.insert(name, mtime);
+                }
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Recursively scan a directory for markdown files, collecting only
+    /// name/path/mtime metadata (no content parsing). Used by
+    /// [`WikiGraph::sync_from_directory`] to decide which files actually
+    /// need reparsing.
+    fn scan_directory_metadata(
+        &self,
+        base_dir: &Path,
+        current_dir: &Path,
+        results: &mut Vec<(String, PathBuf, SystemTime)>,
+    ) -> io::Result<()> {
+        if !current_dir.is_dir() {
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(current_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                self.scan_directory_metadata(base_dir, &path, results)?;
+            } else if path.extension().map_or(false, |ext| ext == "md") {
+                let relative_path = path.strip_prefix(base_dir).unwrap_or(&path).to_path_buf();
+                let name = name_from_relative_path(&relative_path);
+                let last_modified = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .unwrap_or_else(|_| SystemTime::now());
+
+                results.push((name, relative_path, last_modified));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Recursively scan `current_dir` (under `base_dir`) for markdown files,
+/// parsing each one. Free function rather than a `&self` method so it can
+/// be called from [`WikiGraph::build_from_directory`] alongside a
+/// concurrent `&self.profiler` borrow.
+fn scan_directory(
+    base_dir: &Path,
+    current_dir: &Path,
+    results: &mut Vec<(String, PathBuf, ParsedPageData)>,
+    profiler: &RefCell<Profiler>,
+) -> io::Result<()> {
+    if !current_dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(current_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            scan_directory(base_dir, &path, results, profiler)?;
+        } else if path.extension().map_or(false, |ext| ext == "md") {
+            // Get the relative path from base_dir
+            let relative_path = path.strip_prefix(base_dir).unwrap_or(&path).to_path_buf();
+
+            // Derive the hierarchical page name from the relative path
+            // (without .md extension), e.g. "blog/intro.md" -> "blog/intro"
+            let name = name_from_relative_path(&relative_path);
+
+            // Get file modification time
+            let last_modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or_else(|_| SystemTime::now());
+
+            // Read and parse the file
+            match fs::read_to_string(&path) {
+                Ok(content) => {
+                    let parse_start = Instant::now();
+                    let parsed = parse_markdown(&content);
+                    profiler.borrow_mut().record("parse", parse_start.elapsed());
+                    let assets = find_related_assets(base_dir, &relative_path);
+                    results.push((
+                        name,
+                        relative_path,
+                        ParsedPageData {
+                            metadata: parsed.metadata,
+                            links: parsed.links,
+                            embeds: parsed.embeds,
+                            last_modified,
+                            assets,
+                        },
+                    ));
+                }
+                Err(e) => {
+                    eprintln!("Warning: Failed to read {}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl Default for WikiGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Internal struct to hold parsed page data during directory scan.
+struct ParsedPageData {
+    metadata: HashMap<String, Vec<String>>,
+    links: Vec<ParsedLink>,
+    embeds: Vec<ParsedLink>,
+    last_modified: SystemTime,
+    assets: Vec<PathBuf>,
+}
+
+/// Discover the non-`.md` files associated with the page at `relative_path`
+/// (relative to `base_dir`), following Zola's `find_related_assets`: when
+/// the page is the sole markdown file in its directory (a "page bundle"),
+/// every other file sitting beside it is claimed as an asset; a directory
+/// holding more than one page claims none of its siblings, since there's
+/// no single owner. Separately, if a directory shares the page's name
+/// (e.g. `TestPage/` next to `TestPage.md`), every file inside it is
+/// claimed regardless. Returned paths are relative to `base_dir`, matching
+/// [`PageNode::file_path`].
+fn find_related_assets(base_dir: &Path, relative_path: &Path) -> Vec<PathBuf> {
+    let mut assets = Vec::new();
+    let absolute_path = base_dir.join(relative_path);
+
+    if let Some(parent) = absolute_path.parent() {
+        if let Ok(entries) = fs::read_dir(parent) {
+            let entries: Vec<_> = entries.flatten().collect();
+            let markdown_siblings = entries
+                .iter()
+                .filter(|entry| entry.path().extension().map_or(false, |ext| ext == "md"))
+                .count();
+
+            // Only treat this as a Zola-style page bundle (sharing the
+            // directory's non-.md files) when this page is the sole
+            // markdown file there; otherwise a directory of unrelated
+            // pages plus a shared file would have every page claim it.
+            if markdown_siblings == 1 {
+                for entry in entries {
+                    let path = entry.path();
+                    if path.is_file() && path.extension().map_or(true, |ext| ext != "md") {
+                        if let Ok(rel) = path.strip_prefix(base_dir) {
+                            assets.push(rel.to_path_buf());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let sibling_dir = absolute_path.with_extension("");
+    if sibling_dir.is_dir() {
+        if let Ok(entries) = fs::read_dir(&sibling_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_file() {
+                    if let Ok(rel) = path.strip_prefix(base_dir) {
+                        assets.push(rel.to_path_buf());
+                    }
+                }
+            }
+        }
+    }
+
+    assets
+}
+
+/// Derive a hierarchical page name from a path relative to the wiki root,
+/// joining directory components with `/` and stripping the `.md`
+/// extension, e.g. `blog/intro.md` -> `blog/intro`.
+pub(crate) fn name_from_relative_path(relative: &Path) -> String {
+    let mut stem = relative.to_path_buf();
+    stem.set_extension("");
+    stem.components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Escape a string for use inside a DOT quoted identifier.
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Escape a string for use inside an XML attribute or text node.
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Escape a string for use inside a JSON string literal.
+fn escape_json(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Build a DOT node label from a page name and its selected MetaTable columns.
+fn dot_node_label(
+    name: &str,
+    values: &HashMap<String, Vec<String>>,
+    columns: &[String],
+) -> String {
+    let mut label = escape_dot(name);
+    for column in columns {
+        if column == "name" {
+            continue;
+        }
+        if let Some(vals) = values.get(column) {
+            label.push_str(&format!("\\n{}: {}", escape_dot(column), escape_dot(&vals.join(", "))));
+        }
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_new_graph() {
+        let graph = WikiGraph::new();
+        assert_eq!(graph.page_count(), 0);
+        assert_eq!(graph.link_count(), 0);
+    }
+
+    #[test]
+    fn test_add_page() {
+        let mut graph = WikiGraph::new();
+        let page = PageNode::new("TestPage".to_string(), PathBuf::from("TestPage.md"));
+        let _idx = graph.add_page(page);
+
+        assert_eq!(graph.page_count(), 1);
+        assert!(graph.page_exists("TestPage"));
+        assert!(graph.get_page("TestPage").is_some());
+    }
+
+    #[test]
+    fn test_add_page_update() {
+        let mut graph = WikiGraph::new();
+
+        let page1 = PageNode::new("TestPage".to_string(), PathBuf::from("old.md"));
+        graph.add_page(page1);
+
+        let page2 = PageNode::new("TestPage".to_string(), PathBuf::from("new.md"));
+        graph.add_page(page2);
+
+        assert_eq!(graph.page_count(), 1);
+        assert_eq!(
+            graph.get_page("TestPage").unwrap().file_path,
+            PathBuf::from("new.md")
+        );
+    }
+
+    #[test]
+    fn test_list_pages() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Page1".to_string(), PathBuf::from("1.md")));
+        graph.add_page(PageNode::new("Page2".to_string(), PathBuf::from("2.md")));
+
+        let pages = graph.list_pages();
+        assert_eq!(pages.len(), 2);
+
+        let names: Vec<&str> = pages.iter().map(|p| p.name.as_str()).collect();
+        assert!(names.contains(&"Page1"));
+        assert!(names.contains(&"Page2"));
+    }
+
+    #[test]
+    fn test_add_link() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Page1".to_string(), PathBuf::from("1.md")));
+        graph.add_page(PageNode::new("Page2".to_string(), PathBuf::from("2.md")));
+
+        assert!(graph.add_link("Page1", "Page2", WikiLink::new()));
+        assert_eq!(graph.link_count(), 1);
+    }
+
+    #[test]
+    fn test_add_link_missing_page() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Page1".to_string(), PathBuf::from("1.md")));
+
+        // Link to non-existent page should fail
+        assert!(!graph.add_link("Page1", "NonExistent", WikiLink::new()));
+        assert_eq!(graph.link_count(), 0);
+    }
+
+    #[test]
+    fn test_get_backlinks() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("HomePage".to_string(), PathBuf::from("home.md")));
+        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("about.md")));
+        graph.add_page(PageNode::new("Contact".to_string(), PathBuf::from("contact.md")));
+
+        // About and Contact both link to HomePage
+        graph.add_link("About", "HomePage", WikiLink::new());
+        graph.add_link("Contact", "HomePage", WikiLink::new());
+
+        let backlinks = graph.get_backlinks("HomePage");
+        assert_eq!(backlinks.len(), 2);
+        assert!(backlinks.contains(&"About".to_string()));
+        assert!(backlinks.contains(&"Contact".to_string()));
+    }
+
+    #[test]
+    fn test_get_backlinks_none() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Orphan".to_string(), PathBuf::from("orphan.md")));
+
+        let backlinks = graph.get_backlinks("Orphan");
+        assert!(backlinks.is_empty());
+    }
+
+    #[test]
+    fn test_get_backlinks_nonexistent() {
+        let graph = WikiGraph::new();
+        let backlinks = graph.get_backlinks("NonExistent");
+        assert!(backlinks.is_empty());
+    }
+
+    #[test]
+    fn test_backlinks_returns_page_nodes() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("HomePage".to_string(), PathBuf::from("home.md")));
+        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("about.md")));
+        graph.add_page(PageNode::new("Contact".to_string(), PathBuf::from("contact.md")));
+
+        graph.add_link("About", "HomePage", WikiLink::new());
+        graph.add_link("Contact", "HomePage", WikiLink::new());
+
+        let backlinks = graph.backlinks("HomePage");
+        let names: Vec<&str> = backlinks.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"About"));
+        assert!(names.contains(&"Contact"));
+    }
+
+    #[test]
+    fn test_update_page_records_outbound_links() {
+        let mut graph = WikiGraph::new();
+        let links = vec![ParsedLink::new("About".to_string(), None)];
+
+        graph.update_page(
+            "Index",
+            PathBuf::from("index.md"),
+            HashMap::new(),
+            links,
+            SystemTime::now(),
+        );
+
+        let page = graph.get_page("Index").unwrap();
+        assert_eq!(page.outbound_links().len(), 1);
+        assert_eq!(page.outbound_links()[0].target, "About");
+    }
+
+    #[test]
+    fn test_update_page_with_embeds_creates_transclusion_edge() {
+        let mut graph = WikiGraph::new();
+        let embeds = vec![ParsedLink::new("Snippet".to_string(), None)];
+
+        graph.update_page_with_embeds(
+            "Index",
+            PathBuf::from("index.md"),
+            HashMap::new(),
+            Vec::new(),
+            embeds,
+            SystemTime::now(),
+        );
+
+        assert!(graph.page_exists("Snippet"));
+        assert_eq!(
+            graph.link_kind("Index", "Snippet"),
+            Some(LinkKind::Transclusion)
+        );
+    }
+
+    #[test]
+    fn test_update_page_with_embeds_does_not_emit_link_events() {
+        let mut graph = WikiGraph::new();
+        let embeds = vec![ParsedLink::new("Snippet".to_string(), None)];
+
+        let events = graph.update_page_with_embeds(
+            "Index",
+            PathBuf::from("index.md"),
+            HashMap::new(),
+            Vec::new(),
+            embeds,
+            SystemTime::now(),
+        );
+
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::LinkCreated { .. })));
+    }
+
+    #[test]
+    fn test_update_page_with_embeds_navigational_link_unaffected_by_stable_embed() {
+        let mut graph = WikiGraph::new();
+        let links = vec![ParsedLink::new("About".to_string(), None)];
+        let embeds = vec![ParsedLink::new("Snippet".to_string(), None)];
+
+        graph.update_page_with_embeds(
+            "Index",
+            PathBuf::from("index.md"),
+            HashMap::new(),
+            links.clone(),
+            embeds.clone(),
+            SystemTime::now(),
+        );
+
+        // Re-running with the same links/embeds shouldn't report the
+        // unchanged embed as a removed navigational link.
+        let events = graph.update_page_with_embeds(
+            "Index",
+            PathBuf::from("index.md"),
+            HashMap::new(),
+            links,
+            embeds,
+            SystemTime::now(),
+        );
+
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::LinkRemoved { .. })));
+        assert_eq!(
+            graph.link_kind("Index", "Snippet"),
+            Some(LinkKind::Transclusion)
+        );
+        assert_eq!(
+            graph.link_kind("Index", "About"),
+            Some(LinkKind::Navigational)
+        );
+    }
+
+    #[test]
+    fn test_get_outlinks() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Index".to_string(), PathBuf::from("index.md")));
+        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("about.md")));
+        graph.add_page(PageNode::new("Contact".to_string(), PathBuf::from("contact.md")));
+
+        // Index links to both About and Contact
+        graph.add_link("Index", "About", WikiLink::new());
+        graph.add_link("Index", "Contact", WikiLink::new());
+
+        let outlinks = graph.get_outlinks("Index");
+        assert_eq!(outlinks.len(), 2);
+        assert!(outlinks.contains(&"About".to_string()));
+        assert!(outlinks.contains(&"Contact".to_string()));
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Page1".to_string(), PathBuf::from("1.md")));
+        graph.add_page(PageNode::new("Page2".to_string(), PathBuf::from("2.md")));
+        graph.add_link("Page1", "Page2", WikiLink::new());
+
+        assert_eq!(graph.page_count(), 2);
+        assert_eq!(graph.link_count(), 1);
+
+        graph.clear();
+
+        assert_eq!(graph.page_count(), 0);
+        assert_eq!(graph.link_count(), 0);
+        assert!(!graph.page_exists("Page1"));
+    }
+
+    #[test]
+    fn test_name_from_relative_path() {
+        assert_eq!(name_from_relative_path(&PathBuf::from("intro.md")), "intro");
+        assert_eq!(
+            name_from_relative_path(&PathBuf::from("blog/intro.md")),
+            "blog/intro"
+        );
+    }
+
+    #[test]
+    fn test_build_from_directory_subdirectories_dont_collide() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("docs")).unwrap();
+        fs::create_dir(temp_dir.path().join("blog")).unwrap();
+        fs::write(temp_dir.path().join("docs").join("intro.md"), "# Docs intro").unwrap();
+        fs::write(temp_dir.path().join("blog").join("intro.md"), "# Blog intro").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.build_from_directory(temp_dir.path()).unwrap();
+
+        assert_eq!(graph.page_count(), 2);
+        assert!(graph.page_exists("docs/intro"));
+        assert!(graph.page_exists("blog/intro"));
+    }
+
+    #[test]
+    fn test_build_from_directory_collects_sibling_assets() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("TestPage.md"), "# Test Page").unwrap();
+        fs::write(temp_dir.path().join("diagram.png"), b"not a real png").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.build_from_directory(temp_dir.path()).unwrap();
+
+        let assets = &graph.get_page("TestPage").unwrap().assets;
+        assert_eq!(assets, &[PathBuf::from("diagram.png")]);
+    }
+
+    #[test]
+    fn test_build_from_directory_shared_file_not_claimed_by_multiple_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Blog.md"), "# Blog").unwrap();
+        fs::write(temp_dir.path().join("About.md"), "# About").unwrap();
+        fs::write(temp_dir.path().join("logo.png"), b"not a real png").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.build_from_directory(temp_dir.path()).unwrap();
+
+        assert!(graph.get_page("Blog").unwrap().assets.is_empty());
+        assert!(graph.get_page("About").unwrap().assets.is_empty());
+    }
+
+    #[test]
+    fn test_build_from_directory_collects_sibling_directory_assets() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("TestPage.md"), "# Test Page").unwrap();
+        fs::create_dir(temp_dir.path().join("TestPage")).unwrap();
+        fs::write(temp_dir.path().join("TestPage").join("photo.jpg"), b"jpg").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.build_from_directory(temp_dir.path()).unwrap();
+
+        let assets = &graph.get_page("TestPage").unwrap().assets;
+        assert_eq!(assets, &[PathBuf::from("TestPage/photo.jpg")]);
+    }
+
+    #[test]
+    fn test_resolve_bestlink_prefers_most_local_match() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new(
+            "support/Intro".to_string(),
+            PathBuf::from("support/Intro.md"),
+        ));
+        graph.add_page(PageNode::new(
+            "support/FAQ".to_string(),
+            PathBuf::from("support/FAQ.md"),
+        ));
+        graph.add_page(PageNode::new("FAQ".to_string(), PathBuf::from("FAQ.md")));
+
+        assert_eq!(
+            graph.resolve_bestlink("support/Intro", "FAQ"),
+            "support/FAQ"
+        );
+    }
+
+    #[test]
+    fn test_resolve_bestlink_falls_back_to_root() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new(
+            "support/Intro".to_string(),
+            PathBuf::from("support/Intro.md"),
+        ));
+        graph.add_page(PageNode::new("FAQ".to_string(), PathBuf::from("FAQ.md")));
+
+        assert_eq!(graph.resolve_bestlink("support/Intro", "FAQ"), "FAQ");
+    }
+
+    #[test]
+    fn test_resolve_bestlink_creates_stub_as_sibling() {
+        let graph = WikiGraph::new();
+        assert_eq!(
+            graph.resolve_bestlink("support/Intro", "FAQ"),
+            "support/FAQ"
+        );
+        assert_eq!(graph.resolve_bestlink("Intro", "FAQ"), "FAQ");
+    }
+
+    #[test]
+    fn test_resolve_bestlink_absolute_bypasses_search() {
+        let graph = WikiGraph::new();
+        assert_eq!(graph.resolve_bestlink("support/Intro", "/FAQ"), "FAQ");
+    }
+
+    #[test]
+    fn test_resolve_bestlink_empty_target_resolves_to_from() {
+        let graph = WikiGraph::new();
+        assert_eq!(graph.resolve_bestlink("support/Intro", ""), "support/Intro");
+    }
+
+    #[test]
+    fn test_update_page_with_bare_anchor_link_creates_no_phantom_stub() {
+        let mut graph = WikiGraph::new();
+        let link = ParsedLink::with_anchor(String::new(), None, Some("Section".to_string()));
+        graph.update_page(
+            "Intro",
+            PathBuf::from("Intro.md"),
+            HashMap::new(),
+            vec![link],
+            SystemTime::now(),
+        );
+
+        assert_eq!(graph.page_count(), 1);
+        assert!(!graph.page_exists(""));
+    }
+
+    #[test]
+    fn test_resolve_link_matches_bestlink_without_mutating_graph() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new(
+            "support/Intro".to_string(),
+            PathBuf::from("support/Intro.md"),
+        ));
+        graph.add_page(PageNode::new(
+            "support/FAQ".to_string(),
+            PathBuf::from("support/FAQ.md"),
+        ));
+
+        let source_page = graph.get_page("support/Intro").unwrap().clone();
+        let link = ParsedLink::new("FAQ".to_string(), None);
+
+        assert_eq!(graph.resolve_link(&source_page, &link), "support/FAQ");
+        // Read-only: no stub was created and no edge was added.
+        assert_eq!(graph.page_count(), 2);
+        assert!(graph.get_outlinks("support/Intro").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_link_href_appends_anchor() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new(
+            "support/Intro".to_string(),
+            PathBuf::from("support/Intro.md"),
+        ));
+        graph.add_page(PageNode::new(
+            "support/FAQ".to_string(),
+            PathBuf::from("support/FAQ.md"),
+        ));
+
+        let source_page = graph.get_page("support/Intro").unwrap().clone();
+        let link = ParsedLink::with_anchor("FAQ".to_string(), None, Some("Shipping".to_string()));
+
+        assert_eq!(
+            graph.resolve_link_href(&source_page, &link),
+            "support/FAQ#Shipping"
+        );
+    }
+
+    #[test]
+    fn test_resolve_link_href_bare_anchor_targets_same_page() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new(
+            "support/Intro".to_string(),
+            PathBuf::from("support/Intro.md"),
+        ));
+
+        let source_page = graph.get_page("support/Intro").unwrap().clone();
+        let link = ParsedLink::with_anchor(String::new(), None, Some("Section".to_string()));
+
+        assert_eq!(
+            graph.resolve_link_href(&source_page, &link),
+            "support/Intro#Section"
+        );
+    }
+
+    #[test]
+    fn test_resolve_link_href_without_anchor_is_plain_name() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new(
+            "support/Intro".to_string(),
+            PathBuf::from("support/Intro.md"),
+        ));
+        graph.add_page(PageNode::new(
+            "support/FAQ".to_string(),
+            PathBuf::from("support/FAQ.md"),
+        ));
+
+        let source_page = graph.get_page("support/Intro").unwrap().clone();
+        let link = ParsedLink::new("FAQ".to_string(), None);
+
+        assert_eq!(graph.resolve_link_href(&source_page, &link), "support/FAQ");
+    }
+
+    #[test]
+    fn test_update_page_link_uses_bestlink() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new(
+            "support/FAQ".to_string(),
+            PathBuf::from("support/FAQ.md"),
+        ));
+
+        let events = graph.update_page(
+            "support/Intro",
+            PathBuf::from("support/Intro.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("FAQ".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        assert!(graph.get_outlinks("support/Intro").contains(&"support/FAQ".to_string()));
+        assert!(events.iter().any(
+            |e| matches!(e, GraphEvent::LinkCreated { from, to } if from == "support/Intro" && to == "support/FAQ")
+        ));
+    }
+
+    #[test]
+    fn test_sync_from_directory_no_changes_produces_no_events() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A\n\n[[B]]").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.build_from_directory(temp_dir.path()).unwrap();
+        assert!(graph.page_exists("A"));
+        assert!(graph.page_exists("B")); // stub created for the link target
+
+        let events = graph.sync_from_directory(temp_dir.path()).unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_sync_from_directory_reparses_on_mtime_change() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A\n\n[[B]]").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.build_from_directory(temp_dir.path()).unwrap();
+
+        // Ensure the mtime actually advances past filesystem timestamp resolution.
+        sleep(Duration::from_millis(1100));
+        fs::write(temp_dir.path().join("A.md"), "# A\n\nNo links now").unwrap();
+
+        let events = graph.sync_from_directory(temp_dir.path()).unwrap();
+
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageUpdated { name } if name == "A")));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::LinkRemoved { from, to } if from == "A" && to == "B")));
+    }
+
+    #[test]
+    fn test_sync_from_directory_detects_added_and_removed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.build_from_directory(temp_dir.path()).unwrap();
+
+        fs::write(temp_dir.path().join("B.md"), "# B").unwrap();
+        fs::remove_file(temp_dir.path().join("A.md")).unwrap();
+
+        let events = graph.sync_from_directory(temp_dir.path()).unwrap();
+
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageCreated { name } if name == "B")));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageDeleted { name } if name == "A")));
+        assert!(!graph.page_exists("A"));
+        assert!(graph.page_exists("B"));
+    }
+
+    #[test]
+    fn test_sync_from_directory_demotes_deleted_page_with_backlinks_to_stub() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A\n\n[[B]]").unwrap();
+        fs::write(temp_dir.path().join("B.md"), "# B").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.build_from_directory(temp_dir.path()).unwrap();
+        assert!(!graph
+            .broken_links()
+            .iter()
+            .any(|(_, to)| to == "B"));
+
+        fs::remove_file(temp_dir.path().join("B.md")).unwrap();
+        let events = graph.sync_from_directory(temp_dir.path()).unwrap();
+
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageDeleted { name } if name == "B")));
+        // Demoted, not removed: the page still exists as a stub, so the
+        // dangling reference from A is reported rather than vanishing.
+        assert!(graph.page_exists("B"));
+        assert!(graph.get_page("B").unwrap().is_stub);
+        assert!(graph.broken_links().iter().any(|(_, to)| to == "B"));
+    }
+
+    #[test]
+    fn test_rebuild_incremental_parses_all_files_from_an_empty_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A\n\n[[B]]").unwrap();
+
+        let mut graph = WikiGraph::new();
+        let (events, stats) = graph.rebuild_incremental(temp_dir.path()).unwrap();
+
+        assert!(graph.page_exists("A"));
+        assert!(graph.page_exists("B")); // stub created for the link target
+        assert_eq!(stats.changed, 1);
+        assert_eq!(stats.skipped, 0);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageCreated { name } if name == "A")));
+    }
+
+    #[test]
+    fn test_rebuild_incremental_skips_file_whose_content_is_unchanged() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.rebuild_incremental(temp_dir.path()).unwrap();
+
+        // Rewrite the exact same bytes; mtime advances but content doesn't.
+        sleep(Duration::from_millis(1100));
+        fs::write(temp_dir.path().join("A.md"), "# A").unwrap();
+
+        let (events, stats) = graph.rebuild_incremental(temp_dir.path()).unwrap();
+
+        assert!(events.is_empty());
+        assert_eq!(stats.changed, 0);
+        assert_eq!(stats.skipped, 1);
+    }
+
+    #[test]
+    fn test_rebuild_incremental_reparses_file_whose_content_changed() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A\n\n[[B]]").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.rebuild_incremental(temp_dir.path()).unwrap();
+
+        sleep(Duration::from_millis(1100));
+        fs::write(temp_dir.path().join("A.md"), "# A\n\nNo links now").unwrap();
+
+        let (events, stats) = graph.rebuild_incremental(temp_dir.path()).unwrap();
+
+        assert_eq!(stats.changed, 1);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageUpdated { name } if name == "A")));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::LinkRemoved { from, to } if from == "A" && to == "B")));
+    }
+
+    #[test]
+    fn test_rebuild_incremental_removes_pages_for_deleted_files() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.rebuild_incremental(temp_dir.path()).unwrap();
+
+        fs::remove_file(temp_dir.path().join("A.md")).unwrap();
+
+        let (events, stats) = graph.rebuild_incremental(temp_dir.path()).unwrap();
+
+        assert_eq!(stats.removed, 1);
+        assert!(!graph.page_exists("A"));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageDeleted { name } if name == "A")));
+    }
+
+    #[test]
+    fn test_rebuild_incremental_demotes_deleted_page_with_backlinks_to_stub() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A\n\n[[B]]").unwrap();
+        fs::write(temp_dir.path().join("B.md"), "# B").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.rebuild_incremental(temp_dir.path()).unwrap();
+
+        fs::remove_file(temp_dir.path().join("B.md")).unwrap();
+        let (events, stats) = graph.rebuild_incremental(temp_dir.path()).unwrap();
+
+        assert_eq!(stats.removed, 1);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageDeleted { name } if name == "B")));
+        // Demoted, not removed: A's dangling reference to B must still show up.
+        assert!(graph.page_exists("B"));
+        assert!(graph.get_page("B").unwrap().is_stub);
+        assert!(graph.broken_links().iter().any(|(_, to)| to == "B"));
+    }
+
+    #[test]
+    fn test_rebuild_incremental_persists_content_hash_on_the_page_node() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A").unwrap();
+
+        let mut graph = WikiGraph::new();
+        graph.rebuild_incremental(temp_dir.path()).unwrap();
+
+        let node = graph.get_page("A").unwrap();
+        assert!(node.content_hash.is_some());
+    }
+
+    #[test]
+    fn test_remove_page() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Page1".to_string(), PathBuf::from("1.md")));
+        graph.add_page(PageNode::new("Page2".to_string(), PathBuf::from("2.md")));
+        graph.add_link("Page1", "Page2", WikiLink::new());
+
+        assert!(graph.remove_page("Page1"));
+        assert!(!graph.page_exists("Page1"));
+        assert_eq!(graph.page_count(), 1);
+        assert_eq!(graph.link_count(), 0); // Link should be removed too
+    }
+
+    #[test]
+    fn test_remove_page_nonexistent() {
+        let mut graph = WikiGraph::new();
+        assert!(!graph.remove_page("NonExistent"));
+    }
+
+    #[test]
+    fn test_rename_page_preserves_outlinks_and_backlinks() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Old".to_string(), PathBuf::from("old.md")));
+        graph.add_page(PageNode::new("Other".to_string(), PathBuf::from("other.md")));
+        graph.add_link("Old", "Other", WikiLink::new());
+        graph.add_link("Other", "Old", WikiLink::new());
+
+        assert!(graph.rename_page("Old", "New", PathBuf::from("new.md")));
+
+        assert!(!graph.page_exists("Old"));
+        assert!(graph.page_exists("New"));
+        assert_eq!(graph.get_page("New").unwrap().file_path, PathBuf::from("new.md"));
+        assert_eq!(graph.get_outlinks("New"), vec!["Other".to_string()]);
+        assert_eq!(graph.get_backlinks("New"), vec!["Other".to_string()]);
+        assert_eq!(graph.get_outlinks("Other"), vec!["New".to_string()]);
+    }
+
+    #[test]
+    fn test_rename_page_nonexistent_returns_false() {
+        let mut graph = WikiGraph::new();
+        assert!(!graph.rename_page("Missing", "New", PathBuf::from("new.md")));
+    }
+
+    #[test]
+    fn test_remove_page_updates_index() {
+        // Test that node_index is correctly updated after swap-remove
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("First".to_string(), PathBuf::from("1.md")));
+        graph.add_page(PageNode::new("Second".to_string(), PathBuf::from("2.md")));
+        graph.add_page(PageNode::new("Third".to_string(), PathBuf::from("3.md")));
+
+        // Remove first page - Third should be swapped into its position
+        graph.remove_page("First");
+
+        assert!(!graph.page_exists("First"));
+        assert!(graph.page_exists("Second"));
+        assert!(graph.page_exists("Third"));
+        assert_eq!(graph.page_count(), 2);
+
+        // Verify we can still get the pages correctly
+        assert!(graph.get_page("Second").is_some());
+        assert!(graph.get_page("Third").is_some());
+    }
+
+    #[test]
+    fn test_remove_outgoing_edges() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Source".to_string(), PathBuf::from("s.md")));
+        graph.add_page(PageNode::new("Target1".to_string(), PathBuf::from("t1.md")));
+        graph.add_page(PageNode::new("Target2".to_string(), PathBuf::from("t2.md")));
+
+        graph.add_link("Source", "Target1", WikiLink::new());
+        graph.add_link("Source", "Target2", WikiLink::new());
+
+        assert_eq!(graph.link_count(), 2);
+        assert_eq!(graph.get_outlinks("Source").len(), 2);
+
+        graph.remove_outgoing_edges("Source");
+
+        assert_eq!(graph.link_count(), 0);
+        assert_eq!(graph.get_outlinks("Source").len(), 0);
+    }
+
+    #[test]
+    fn test_update_page_new() {
+        let mut graph = WikiGraph::new();
+
+        let events = graph.update_page(
+            "NewPage",
+            PathBuf::from("NewPage.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("Target".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        assert!(graph.page_exists("NewPage"));
+        assert!(graph.page_exists("Target")); // Stub created
+        assert_eq!(graph.link_count(), 1);
+
+        // Should have a LinkCreated event plus the matching BacklinkChanged
+        // for the target.
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().any(
+            |e| matches!(e, GraphEvent::LinkCreated { from, to } if from == "NewPage" && to == "Target")
+        ));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::BacklinkChanged { page } if page == "Target")));
+    }
+
+    #[test]
+    fn test_update_page_modify_links() {
+        let mut graph = WikiGraph::new();
+
+        // Create initial page with links to A and B
+        graph.update_page(
+            "Test",
+            PathBuf::from("Test.md"),
+            HashMap::new(),
+            vec![
+                ParsedLink::new("A".to_string(), None),
+                ParsedLink::new("B".to_string(), None),
+            ],
+            SystemTime::now(),
+        );
+
+        assert_eq!(graph.get_outlinks("Test").len(), 2);
+
+        // Update: remove link to A, add link to C
+        let events = graph.update_page(
+            "Test",
+            PathBuf::from("Test.md"),
+            HashMap::new(),
+            vec![
+                ParsedLink::new("B".to_string(), None),
+                ParsedLink::new("C".to_string(), None),
+            ],
+            SystemTime::now(),
+        );
+
+        // Should have LinkRemoved(A) and LinkCreated(C)
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::LinkRemoved { from, to } if from == "Test" && to == "A")));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::LinkCreated { from, to } if from == "Test" && to == "C")));
+
+        // B should not generate events (unchanged)
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::LinkCreated { to, .. } | GraphEvent::LinkRemoved { to, .. } if to == "B")));
+    }
+
+    #[test]
+    fn test_update_page_no_changes() {
+        let mut graph = WikiGraph::new();
+
+        // Create initial page with link to A
+        graph.update_page(
+            "Test",
+            PathBuf::from("Test.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("A".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        // Update with same links
+        let events = graph.update_page(
+            "Test",
+            PathBuf::from("Test.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("A".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        // No link change events
+        assert!(events.is_empty());
+    }
+
+    fn star_graph() -> WikiGraph {
+        let mut graph = WikiGraph::new();
+        for name in ["Hub", "A", "B", "C", "Island"] {
+            graph.add_page(PageNode::new(name.to_string(), PathBuf::from(format!("{}.md", name))));
+        }
+        graph.add_link("Hub", "A", WikiLink::new());
+        graph.add_link("Hub", "B", WikiLink::new());
+        graph.add_link("B", "C", WikiLink::new());
+        graph
+    }
+
+    #[test]
+    fn test_undirected_distances_from() {
+        let graph = star_graph();
+        let distances = graph.undirected_distances_from("Hub");
+
+        assert_eq!(distances.get("Hub"), Some(&0));
+        assert_eq!(distances.get("A"), Some(&1));
+        assert_eq!(distances.get("B"), Some(&1));
+        assert_eq!(distances.get("C"), Some(&2));
+        assert_eq!(distances.get("Island"), None);
+    }
+
+    #[test]
+    fn test_metatable_ranked_orders_by_distance() {
+        let graph = star_graph();
+        let columns = vec!["name".to_string()];
+        let result = graph.metatable_ranked(&[], &columns, "Hub", 1);
+
+        let names: Vec<&str> = result.rows.iter().map(|r| r.page_name.as_str()).collect();
+        // Hub (0), A and B (1), C (2), Island (unreachable) last
+        assert_eq!(names[0], "Hub");
+        assert_eq!(names.last(), Some(&"Island"));
+        assert_eq!(result.rows[0].distance, Some(0));
+        assert_eq!(
+            result
+                .rows
+                .iter()
+                .find(|r| r.page_name == "Island")
+                .unwrap()
+                .distance,
+            None
+        );
+    }
+
+    #[test]
+    fn test_k_shortest_paths() {
+        let graph = star_graph();
+        let paths = graph.k_shortest_paths("A", "C", 2);
+
+        assert!(!paths.is_empty());
+        assert_eq!(paths[0], vec!["A".to_string(), "Hub".to_string(), "B".to_string(), "C".to_string()]);
+    }
+
+    #[test]
+    fn test_k_shortest_paths_unreachable() {
+        let graph = star_graph();
+        let paths = graph.k_shortest_paths("A", "Island", 2);
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn test_to_dot_matched_only() {
+        let graph = star_graph();
+        let columns = vec!["name".to_string()];
+        let result = graph.metatable(&[], &columns);
+
+        let dot = graph.to_dot(&result, false);
+        assert!(dot.starts_with("digraph wiki {"));
+        assert!(dot.contains("\"Hub\""));
+        assert!(dot.contains("\"Hub\" -> \"A\""));
+        assert!(dot.contains("\"Hub\" -> \"B\""));
+        assert!(dot.contains("\"B\" -> \"C\""));
+    }
+
+    #[test]
+    fn test_to_dot_excludes_unmatched_links_without_neighbors() {
+        let graph = star_graph();
+        let columns = vec!["name".to_string()];
+        let result = graph.metatable(&[], &columns);
+        let matched_only = MetaTableResult {
+            columns: result.columns.clone(),
+            rows: result
+                .rows
+                .into_iter()
+                .filter(|r| r.page_name == "Hub" || r.page_name == "A")
+                .collect(),
+        };
+
+        let dot = graph.to_dot(&matched_only, false);
+        assert!(dot.contains("\"Hub\" -> \"A\""));
+        assert!(!dot.contains("\"Hub\" -> \"B\""));
+    }
+
+    #[test]
+    fn test_metatable_highlighted_contains() {
+        let mut graph = WikiGraph::new();
+        let mut metadata = HashMap::new();
+        metadata.insert("tags".to_string(), vec!["rust-lang".to_string(), "wiki".to_string()]);
+        graph.add_page(PageNode::with_metadata(
+            "Test".to_string(),
+            PathBuf::from("Test.md"),
+            metadata,
+            SystemTime::now(),
+        ));
+
+        let filters = vec![Filter::Contains {
+            key: "tags".to_string(),
+            substring: "rust".to_string(),
+        }];
+        let columns = vec!["name".to_string(), "tags".to_string()];
+        let result = graph.metatable_highlighted(&filters, &columns, "<mark>", "</mark>");
+
+        let row = &result.rows[0];
+        assert_eq!(
+            row.highlighted_values.get("tags"),
+            Some(&vec!["<mark>rust</mark>-lang".to_string(), "wiki".to_string()])
+        );
+        assert_eq!(row.highlights.get("tags"), Some(&vec![(0, 4)]));
+    }
+
+    #[test]
+    fn test_metatable_highlighted_no_predicate_leaves_column_unhighlighted() {
+        let mut graph = WikiGraph::new();
+        let mut metadata = HashMap::new();
+        metadata.insert("status".to_string(), vec!["draft".to_string()]);
+        graph.add_page(PageNode::with_metadata(
+            "Test".to_string(),
+            PathBuf::from("Test.md"),
+            metadata,
+            SystemTime::now(),
+        ));
+
+        let columns = vec!["name".to_string(), "status".to_string()];
+        let result = graph.metatable_highlighted(&[], &columns, "<mark>", "</mark>");
+
+        assert!(result.rows[0].highlights.is_empty());
+    }
+
+    #[test]
+    fn test_pagestats_counts_and_buckets() {
+        let mut graph = WikiGraph::new();
+        let mut tags_rust = HashMap::new();
+        tags_rust.insert("tags".to_string(), vec!["rust".to_string()]);
+        let mut tags_rust_wiki = HashMap::new();
+        tags_rust_wiki.insert(
+            "tags".to_string(),
+            vec!["rust".to_string(), "wiki".to_string()],
+        );
+
+        graph.add_page(PageNode::with_metadata(
+            "A".to_string(),
+            PathBuf::from("A.md"),
+            tags_rust,
+            SystemTime::now(),
+        ));
+        graph.add_page(PageNode::with_metadata(
+            "B".to_string(),
+            PathBuf::from("B.md"),
+            tags_rust_wiki,
+            SystemTime::now(),
+        ));
+        graph.add_page(PageNode::new("C".to_string(), PathBuf::from("C.md"))); // no tags
+
+        let spec = PageSpec::parse("*").unwrap();
+        let stats = graph.pagestats(&spec, "tags");
+
+        assert_eq!(
+            stats,
+            vec![("rust".to_string(), 2, 9), ("wiki".to_string(), 1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_pagestats_equal_counts_get_middle_bucket() {
+        let mut graph = WikiGraph::new();
+        for name in ["A", "B"] {
+            let mut metadata = HashMap::new();
+            metadata.insert("tags".to_string(), vec!["x".to_string(), "y".to_string()]);
+            graph.add_page(PageNode::with_metadata(
+                name.to_string(),
+                PathBuf::from(format!("{}.md", name)),
+                metadata,
+                SystemTime::now(),
+            ));
+        }
+
+        let spec = PageSpec::parse("*").unwrap();
+        let stats = graph.pagestats(&spec, "tags");
+        assert!(stats.iter().all(|(_, count, bucket)| *count == 2 && *bucket == 5));
+    }
+
+    #[test]
+    fn test_pagestats_empty_when_no_matches() {
+        let graph = WikiGraph::new();
+        let spec = PageSpec::parse("*").unwrap();
+        assert!(graph.pagestats(&spec, "tags").is_empty());
+    }
+
+    #[test]
+    fn test_facet_explodes_multi_value_fields_into_separate_buckets() {
+        let mut graph = WikiGraph::new();
+        let mut tags_rust = HashMap::new();
+        tags_rust.insert("tags".to_string(), vec!["rust".to_string()]);
+        let mut tags_rust_wiki = HashMap::new();
+        tags_rust_wiki.insert(
+            "tags".to_string(),
+            vec!["rust".to_string(), "wiki".to_string()],
+        );
+
+        graph.add_page(PageNode::with_metadata(
+            "A".to_string(),
+            PathBuf::from("A.md"),
+            tags_rust,
+            SystemTime::now(),
+        ));
+        graph.add_page(PageNode::with_metadata(
+            "B".to_string(),
+            PathBuf::from("B.md"),
+            tags_rust_wiki,
+            SystemTime::now(),
+        ));
+        graph.add_page(PageNode::new("C".to_string(), PathBuf::from("C.md"))); // no tags
+
+        let result = graph.facet("tags");
+
+        assert_eq!(result.key, "tags");
+        assert_eq!(result.buckets.len(), 2);
+
+        let rust_bucket = result.buckets.iter().find(|b| b.value == "rust").unwrap();
+        assert_eq!(rust_bucket.count, 2);
+        let mut rust_pages = rust_bucket.pages.clone();
+        rust_pages.sort();
+        assert_eq!(rust_pages, vec!["A".to_string(), "B".to_string()]);
+
+        let wiki_bucket = result.buckets.iter().find(|b| b.value == "wiki").unwrap();
+        assert_eq!(wiki_bucket.count, 1);
+        assert_eq!(wiki_bucket.pages, vec!["B".to_string()]);
+    }
+
+    #[test]
+    fn test_facet_empty_when_no_page_has_the_key() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("A".to_string(), PathBuf::from("A.md")));
+
+        let result = graph.facet("tags");
+        assert!(result.buckets.is_empty());
+    }
+
+    #[test]
+    fn test_facet_counts_runs_facet_for_each_key_in_order() {
+        let mut graph = WikiGraph::new();
+        let mut metadata = HashMap::new();
+        metadata.insert("tags".to_string(), vec!["rust".to_string()]);
+        metadata.insert("status".to_string(), vec!["draft".to_string()]);
+        graph.add_page(PageNode::with_metadata(
+            "A".to_string(),
+            PathBuf::from("A.md"),
+            metadata,
+            SystemTime::now(),
+        ));
+
+        let results = graph.facet_counts(&["tags".to_string(), "status".to_string()]);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].key, "tags");
+        assert_eq!(results[1].key, "status");
+        assert_eq!(results[1].buckets[0].value, "draft");
+    }
+
+    #[test]
+    fn test_map_synthesizes_parent() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("a/b/c".to_string(), PathBuf::from("a/b/c.md")));
+
+        let spec = PageSpec::parse("*").unwrap();
+        let forest = graph.map(&spec);
+
+        assert_eq!(forest.len(), 1);
+        assert_eq!(forest[0].name, "a");
+        assert!(!forest[0].is_real);
+        assert_eq!(forest[0].children[0].children[0].full_name, "a/b/c");
+    }
+
+    #[test]
+    fn test_query_pagespec() {
+        let graph = star_graph();
+        let pages = graph.query_pagespec("link(A)").unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].name, "Hub");
+    }
+
+    #[test]
+    fn test_query_pagespec_invalid() {
+        let graph = star_graph();
+        assert!(graph.query_pagespec("bogus(A)").is_err());
+    }
+
+    #[test]
+    fn test_select_returns_matching_names() {
+        let graph = star_graph();
+        let spec = PageSpec::parse("link(A)").unwrap();
+        assert_eq!(graph.select(&spec), vec!["Hub".to_string()]);
+    }
+
+    #[test]
+    fn test_select_orphan_and_dead_end() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Index".to_string(), PathBuf::from("Index.md")));
+        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        graph.add_link("Index", "About", WikiLink::new());
+
+        let orphan_spec = PageSpec::parse("orphan()").unwrap();
+        assert_eq!(graph.select(&orphan_spec), vec!["Index".to_string()]);
+
+        let dead_end_spec = PageSpec::parse("dead_end()").unwrap();
+        assert_eq!(graph.select(&dead_end_spec), vec!["About".to_string()]);
+    }
+
+    #[test]
+    fn test_to_dot_includes_neighbors_when_requested() {
+        let graph = star_graph();
+        let columns = vec!["name".to_string()];
+        let result = graph.metatable(&[], &columns);
+        let matched_only = MetaTableResult {
+            columns: result.columns.clone(),
+            rows: result
+                .rows
+                .into_iter()
+                .filter(|r| r.page_name == "Hub")
+                .collect(),
+        };
+
+        let dot = graph.to_dot(&matched_only, true);
+        assert!(dot.contains("\"Hub\" -> \"A\""));
+        assert!(dot.contains("\"Hub\" -> \"B\""));
+        assert!(dot.contains("style=dashed"));
+    }
+
+    #[test]
+    fn test_broken_links_reports_stub_targets() {
+        let mut graph = WikiGraph::new();
+        graph.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("Missing".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        let broken = graph.broken_links();
+        assert_eq!(broken, vec![("Home".to_string(), "Missing".to_string())]);
+    }
+
+    #[test]
+    fn test_broken_links_excludes_real_targets() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        graph.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("About".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        assert!(graph.broken_links().is_empty());
+    }
+
+    #[test]
+    fn test_orphans_returns_pages_with_no_backlinks() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        graph.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("About".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        let orphans = graph.orphans();
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].name, "Home");
+    }
+
+    #[test]
+    fn test_to_dot_graph_renders_whole_graph() {
+        let graph = star_graph();
+        let dot = graph.to_dot_graph(&DotOptions::default());
+
+        assert!(dot.contains("\"Hub\" -> \"A\""));
+        assert!(dot.contains("\"Hub\" -> \"B\""));
+        assert!(dot.contains("\"B\" -> \"C\""));
+    }
+
+    #[test]
+    fn test_to_dot_graph_styles_stubs() {
+        let mut graph = WikiGraph::new();
+        graph.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("Missing".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        let dot = graph.to_dot_graph(&DotOptions::default());
+        assert!(dot.contains("\"Missing\" [label=\"Missing\", style=dashed];"));
+        assert!(!dot.contains("\"Home\" [label=\"Home\", style=dashed];"));
+    }
+
+    #[test]
+    fn test_to_dot_graph_labels_edges_with_display_text() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
+        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        graph.add_link(
+            "Home",
+            "About",
+            WikiLink::with_display_text("About us".to_string()),
+        );
+
+        let opts = DotOptions {
+            show_edge_labels: true,
+            ..Default::default()
+        };
+        let dot = graph.to_dot_graph(&opts);
+        assert!(dot.contains("\"Home\" -> \"About\" [label=\"About us\"];"));
+    }
+
+    #[test]
+    fn test_to_dot_graph_restricts_to_hops_from_root() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("A".to_string(), PathBuf::from("A.md")));
+        graph.add_page(PageNode::new("B".to_string(), PathBuf::from("B.md")));
+        graph.add_page(PageNode::new("C".to_string(), PathBuf::from("C.md")));
+        graph.add_link("A", "B", WikiLink::new());
+        graph.add_link("B", "C", WikiLink::new());
+
+        let opts = DotOptions {
+            root: Some("A".to_string()),
+            max_hops: 1,
+            show_edge_labels: false,
+        };
+        let dot = graph.to_dot_graph(&opts);
+        assert!(dot.contains("\"A\" -> \"B\""));
+        assert!(!dot.contains("\"C\""));
+    }
+
+    #[test]
+    fn test_orphans_excludes_stubs() {
+        let mut graph = WikiGraph::new();
+        graph.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("Missing".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        // "Missing" has no real backlinks either, but it's a stub, not an
+        // orphaned real page, so it must not appear in the report.
+        let orphans = graph.orphans();
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].name, "Home");
+    }
+
+    #[test]
+    fn test_connected_none_before_enabling_index() {
+        let mut graph = star_graph();
+        assert_eq!(graph.connected("Hub", "A"), None);
+    }
+
+    #[test]
+    fn test_connected_seeds_from_existing_edges() {
+        let mut graph = star_graph();
+        graph.enable_connectivity_index();
+
+        assert_eq!(graph.connected("Hub", "A"), Some(true));
+        assert_eq!(graph.connected("A", "B"), Some(true));
+        assert_eq!(graph.connected("Hub", "Nowhere"), Some(false));
+    }
+
+    #[test]
+    fn test_connected_tracks_update_page_link_changes() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("A".to_string(), PathBuf::from("A.md")));
+        graph.add_page(PageNode::new("B".to_string(), PathBuf::from("B.md")));
+        graph.enable_connectivity_index();
+
+        assert_eq!(graph.connected("A", "B"), Some(false));
+
+        graph.update_page(
+            "A",
+            PathBuf::from("A.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("B".to_string(), None)],
+            SystemTime::now(),
+        );
+        assert_eq!(graph.connected("A", "B"), Some(true));
+
+        graph.update_page(
+            "A",
+            PathBuf::from("A.md"),
+            HashMap::new(),
+            vec![],
+            SystemTime::now(),
+        );
+        assert_eq!(graph.connected("A", "B"), Some(false));
     }
 
     #[test]
-    fn test_add_page() {
-        let mut graph = WikiGraph::new();
-        let page = PageNode::new("TestPage".to_string(), PathBuf::from("TestPage.md"));
-        let _idx = graph.add_page(page);
+    fn test_connected_drops_edges_on_page_removal() {
+        let mut graph = star_graph();
+        graph.enable_connectivity_index();
+        assert_eq!(graph.connected("A", "B"), Some(true));
 
-        assert_eq!(graph.page_count(), 1);
-        assert!(graph.page_exists("TestPage"));
-        assert!(graph.get_page("TestPage").is_some());
+        graph.remove_page("Hub");
+        assert_eq!(graph.connected("A", "B"), Some(false));
     }
 
     #[test]
-    fn test_add_page_update() {
-        let mut graph = WikiGraph::new();
+    fn test_merge_unions_disjoint_edits() {
+        let mut replica_a = WikiGraph::new_with_replica(1);
+        replica_a.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
+        replica_a.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        replica_a.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("About".to_string(), None)],
+            SystemTime::now(),
+        );
 
-        let page1 = PageNode::new("TestPage".to_string(), PathBuf::from("old.md"));
-        graph.add_page(page1);
+        let mut replica_b = WikiGraph::new_with_replica(2);
+        replica_b.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
+        replica_b.add_page(PageNode::new("Contact".to_string(), PathBuf::from("Contact.md")));
+        replica_b.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("Contact".to_string(), None)],
+            SystemTime::now(),
+        );
 
-        let page2 = PageNode::new("TestPage".to_string(), PathBuf::from("new.md"));
-        graph.add_page(page2);
+        replica_a.merge(&replica_b);
 
-        assert_eq!(graph.page_count(), 1);
+        let outlinks: HashSet<String> = replica_a.get_outlinks("Home").into_iter().collect();
         assert_eq!(
-            graph.get_page("TestPage").unwrap().file_path,
-            PathBuf::from("new.md")
+            outlinks,
+            HashSet::from(["About".to_string(), "Contact".to_string()])
         );
     }
 
     #[test]
-    fn test_list_pages() {
-        let mut graph = WikiGraph::new();
-        graph.add_page(PageNode::new("Page1".to_string(), PathBuf::from("1.md")));
-        graph.add_page(PageNode::new("Page2".to_string(), PathBuf::from("2.md")));
+    fn test_merge_is_add_wins_for_concurrent_add_and_remove() {
+        // Replica A adds then removes a link to "About" entirely on its
+        // own. Replica B, independently and without ever observing A's
+        // edits, adds its own link to "About". Per the OR-Set invariant,
+        // B's add-dot was never covered by A's remove, so it survives the
+        // merge: the link ends up present.
+        let mut replica_a = WikiGraph::new_with_replica(1);
+        replica_a.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
+        replica_a.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        replica_a.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("About".to_string(), None)],
+            SystemTime::now(),
+        );
+        replica_a.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![],
+            SystemTime::now(),
+        );
 
-        let pages = graph.list_pages();
-        assert_eq!(pages.len(), 2);
+        let mut replica_b = WikiGraph::new_with_replica(2);
+        replica_b.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
+        replica_b.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        replica_b.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("About".to_string(), None)],
+            SystemTime::now(),
+        );
 
-        let names: Vec<&str> = pages.iter().map(|p| p.name.as_str()).collect();
-        assert!(names.contains(&"Page1"));
-        assert!(names.contains(&"Page2"));
+        replica_a.merge(&replica_b);
+        assert!(replica_a
+            .get_outlinks("Home")
+            .contains(&"About".to_string()));
     }
 
     #[test]
-    fn test_add_link() {
-        let mut graph = WikiGraph::new();
-        graph.add_page(PageNode::new("Page1".to_string(), PathBuf::from("1.md")));
-        graph.add_page(PageNode::new("Page2".to_string(), PathBuf::from("2.md")));
+    fn test_merge_creates_stub_for_unknown_target() {
+        let mut replica_a = WikiGraph::new_with_replica(1);
+        replica_a.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
 
-        assert!(graph.add_link("Page1", "Page2", WikiLink::new()));
-        assert_eq!(graph.link_count(), 1);
+        let mut replica_b = WikiGraph::new_with_replica(2);
+        replica_b.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
+        replica_b.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("Missing".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        replica_a.merge(&replica_b);
+        assert!(replica_a.page_exists("Missing"));
+        assert!(replica_a.get_page("Missing").unwrap().is_stub);
     }
 
     #[test]
-    fn test_add_link_missing_page() {
-        let mut graph = WikiGraph::new();
-        graph.add_page(PageNode::new("Page1".to_string(), PathBuf::from("1.md")));
+    fn test_merge_never_lets_a_fresher_stub_overwrite_older_real_content() {
+        use std::time::Duration;
 
-        // Link to non-existent page should fail
-        assert!(!graph.add_link("Page1", "NonExistent", WikiLink::new()));
-        assert_eq!(graph.link_count(), 0);
+        // Replica A has a stub (e.g. created for a dangling link), stamped
+        // "now" as PageNode::stub always does.
+        let mut replica_a = WikiGraph::new_with_replica(1);
+        replica_a.add_page(PageNode::stub(
+            "Missing".to_string(),
+            PathBuf::from("Missing.md"),
+        ));
+
+        // Replica B has the real page, but with an *older* last_modified
+        // than the stub above — a naive timestamp comparison would keep
+        // the newer stub and drop the real content.
+        let mut replica_b = WikiGraph::new_with_replica(2);
+        let old_time = SystemTime::now() - Duration::from_secs(3600);
+        let mut real_page = PageNode::with_metadata(
+            "Missing".to_string(),
+            PathBuf::from("Missing.md"),
+            HashMap::new(),
+            old_time,
+        );
+        real_page
+            .metadata
+            .insert("title".to_string(), vec!["Real Page".to_string()]);
+        replica_b.add_page(real_page);
+
+        replica_a.merge(&replica_b);
+
+        let merged = replica_a.get_page("Missing").unwrap();
+        assert!(!merged.is_stub);
+        assert_eq!(
+            merged.metadata.get("title"),
+            Some(&vec!["Real Page".to_string()])
+        );
     }
 
     #[test]
-    fn test_get_backlinks() {
-        let mut graph = WikiGraph::new();
-        graph.add_page(PageNode::new("HomePage".to_string(), PathBuf::from("home.md")));
-        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("about.md")));
-        graph.add_page(PageNode::new("Contact".to_string(), PathBuf::from("contact.md")));
+    fn test_merge_never_lets_an_incoming_stub_overwrite_real_content() {
+        use std::time::Duration;
 
-        // About and Contact both link to HomePage
-        graph.add_link("About", "HomePage", WikiLink::new());
-        graph.add_link("Contact", "HomePage", WikiLink::new());
+        // Replica A has the real page.
+        let mut replica_a = WikiGraph::new_with_replica(1);
+        let mut real_page = PageNode::with_metadata(
+            "Missing".to_string(),
+            PathBuf::from("Missing.md"),
+            HashMap::new(),
+            SystemTime::now(),
+        );
+        real_page
+            .metadata
+            .insert("title".to_string(), vec!["Real Page".to_string()]);
+        replica_a.add_page(real_page);
 
-        let backlinks = graph.get_backlinks("HomePage");
-        assert_eq!(backlinks.len(), 2);
-        assert!(backlinks.contains(&"About".to_string()));
-        assert!(backlinks.contains(&"Contact".to_string()));
+        // Replica B's stub for the same name is, implausibly, stamped
+        // later than A's real edit — still must not win.
+        let mut replica_b = WikiGraph::new_with_replica(2);
+        let mut stub = PageNode::stub("Missing".to_string(), PathBuf::from("Missing.md"));
+        stub.last_modified = SystemTime::now() + Duration::from_secs(3600);
+        replica_b.add_page(stub);
+
+        replica_a.merge(&replica_b);
+
+        let merged = replica_a.get_page("Missing").unwrap();
+        assert!(!merged.is_stub);
+        assert_eq!(
+            merged.metadata.get("title"),
+            Some(&vec!["Real Page".to_string()])
+        );
     }
 
     #[test]
-    fn test_get_backlinks_none() {
-        let mut graph = WikiGraph::new();
-        graph.add_page(PageNode::new("Orphan".to_string(), PathBuf::from("orphan.md")));
+    fn test_merge_emits_link_created_event() {
+        let mut replica_a = WikiGraph::new_with_replica(1);
+        replica_a.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
 
-        let backlinks = graph.get_backlinks("Orphan");
-        assert!(backlinks.is_empty());
+        let mut replica_b = WikiGraph::new_with_replica(2);
+        replica_b.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
+        replica_b.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        replica_b.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
+            HashMap::new(),
+            vec![ParsedLink::new("About".to_string(), None)],
+            SystemTime::now(),
+        );
+
+        let events = replica_a.merge(&replica_b);
+        assert!(events.iter().any(
+            |e| matches!(e, GraphEvent::LinkCreated { from, to } if from == "Home" && to == "About")
+        ));
     }
 
     #[test]
-    fn test_get_backlinks_nonexistent() {
-        let graph = WikiGraph::new();
-        let backlinks = graph.get_backlinks("NonExistent");
-        assert!(backlinks.is_empty());
+    fn test_apply_git_changes_parses_only_added_and_modified_files() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A\n\n[[B]]").unwrap();
+        fs::write(temp_dir.path().join("B.md"), "# B").unwrap();
+
+        let mut graph = WikiGraph::new();
+        let events = graph
+            .apply_git_changes(
+                temp_dir.path(),
+                &[
+                    ChangedFile::Added(PathBuf::from("A.md")),
+                    ChangedFile::Added(PathBuf::from("B.md")),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(graph.page_count(), 2);
+        assert!(graph.get_outlinks("A").contains(&"B".to_string()));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageCreated { name } if name == "A")));
+        assert!(events.iter().any(
+            |e| matches!(e, GraphEvent::LinkCreated { from, to } if from == "A" && to == "B")
+        ));
     }
 
     #[test]
-    fn test_get_outlinks() {
+    fn test_apply_git_changes_removes_deleted_pages() {
         let mut graph = WikiGraph::new();
-        graph.add_page(PageNode::new("Index".to_string(), PathBuf::from("index.md")));
-        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("about.md")));
-        graph.add_page(PageNode::new("Contact".to_string(), PathBuf::from("contact.md")));
+        graph.add_page(PageNode::new("Old".to_string(), PathBuf::from("Old.md")));
 
-        // Index links to both About and Contact
-        graph.add_link("Index", "About", WikiLink::new());
-        graph.add_link("Index", "Contact", WikiLink::new());
+        let temp_dir = TempDir::new().unwrap();
+        let events = graph
+            .apply_git_changes(
+                temp_dir.path(),
+                &[ChangedFile::Deleted(PathBuf::from("Old.md"))],
+            )
+            .unwrap();
 
-        let outlinks = graph.get_outlinks("Index");
-        assert_eq!(outlinks.len(), 2);
-        assert!(outlinks.contains(&"About".to_string()));
-        assert!(outlinks.contains(&"Contact".to_string()));
+        assert!(!graph.page_exists("Old"));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageDeleted { name } if name == "Old")));
     }
 
     #[test]
-    fn test_clear() {
+    fn test_apply_git_changes_demotes_deleted_page_with_backlinks_to_stub() {
         let mut graph = WikiGraph::new();
-        graph.add_page(PageNode::new("Page1".to_string(), PathBuf::from("1.md")));
-        graph.add_page(PageNode::new("Page2".to_string(), PathBuf::from("2.md")));
-        graph.add_link("Page1", "Page2", WikiLink::new());
-
-        assert_eq!(graph.page_count(), 2);
-        assert_eq!(graph.link_count(), 1);
+        graph.add_page(PageNode::new("A".to_string(), PathBuf::from("A.md")));
+        graph.add_page(PageNode::new("B".to_string(), PathBuf::from("B.md")));
+        graph.add_link("A", "B", WikiLink::new());
 
-        graph.clear();
+        let temp_dir = TempDir::new().unwrap();
+        let events = graph
+            .apply_git_changes(
+                temp_dir.path(),
+                &[ChangedFile::Deleted(PathBuf::from("B.md"))],
+            )
+            .unwrap();
 
-        assert_eq!(graph.page_count(), 0);
-        assert_eq!(graph.link_count(), 0);
-        assert!(!graph.page_exists("Page1"));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GraphEvent::PageDeleted { name } if name == "B")));
+        // Demoted, not removed: A's dangling reference to B must still show up.
+        assert!(graph.page_exists("B"));
+        assert!(graph.get_page("B").unwrap().is_stub);
+        assert!(graph.broken_links().iter().any(|(_, to)| to == "B"));
     }
 
-    // Note: build_from_directory tests require tempfile crate
-    // which is added as a dev-dependency
-
     #[test]
-    fn test_remove_page() {
+    fn test_apply_git_changes_reparses_modified_file_link_diff() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A\n\n[[B]]").unwrap();
+
         let mut graph = WikiGraph::new();
-        graph.add_page(PageNode::new("Page1".to_string(), PathBuf::from("1.md")));
-        graph.add_page(PageNode::new("Page2".to_string(), PathBuf::from("2.md")));
-        graph.add_link("Page1", "Page2", WikiLink::new());
+        graph
+            .apply_git_changes(
+                temp_dir.path(),
+                &[ChangedFile::Added(PathBuf::from("A.md"))],
+            )
+            .unwrap();
+        assert!(graph.get_outlinks("A").contains(&"B".to_string()));
 
-        assert!(graph.remove_page("Page1"));
-        assert!(!graph.page_exists("Page1"));
-        assert_eq!(graph.page_count(), 1);
-        assert_eq!(graph.link_count(), 0); // Link should be removed too
+        fs::write(temp_dir.path().join("A.md"), "# A\n\n[[C]]").unwrap();
+        let events = graph
+            .apply_git_changes(
+                temp_dir.path(),
+                &[ChangedFile::Modified(PathBuf::from("A.md"))],
+            )
+            .unwrap();
+
+        assert!(!graph.get_outlinks("A").contains(&"B".to_string()));
+        assert!(graph.get_outlinks("A").contains(&"C".to_string()));
+        assert!(events.iter().any(
+            |e| matches!(e, GraphEvent::LinkRemoved { from, to } if from == "A" && to == "B")
+        ));
     }
 
     #[test]
-    fn test_remove_page_nonexistent() {
-        let mut graph = WikiGraph::new();
-        assert!(!graph.remove_page("NonExistent"));
+    fn test_export_graph_dot_matches_to_dot_graph_edges() {
+        let graph = star_graph();
+        let dot = graph.export_graph(GraphExportFormat::Dot, &GraphExportOptions::default());
+
+        assert!(dot.contains("\"Hub\" -> \"A\""));
+        assert!(dot.contains("\"Hub\" -> \"B\""));
+        assert!(dot.contains("\"B\" -> \"C\""));
     }
 
     #[test]
-    fn test_remove_page_updates_index() {
-        // Test that node_index is correctly updated after swap-remove
+    fn test_export_graph_dot_embeds_requested_metadata() {
         let mut graph = WikiGraph::new();
-        graph.add_page(PageNode::new("First".to_string(), PathBuf::from("1.md")));
-        graph.add_page(PageNode::new("Second".to_string(), PathBuf::from("2.md")));
-        graph.add_page(PageNode::new("Third".to_string(), PathBuf::from("3.md")));
-
-        // Remove first page - Third should be swapped into its position
-        graph.remove_page("First");
-
-        assert!(!graph.page_exists("First"));
-        assert!(graph.page_exists("Second"));
-        assert!(graph.page_exists("Third"));
-        assert_eq!(graph.page_count(), 2);
+        let mut metadata = HashMap::new();
+        metadata.insert("status".to_string(), vec!["draft".to_string()]);
+        graph.add_page(PageNode::with_metadata(
+            "Home".to_string(),
+            PathBuf::from("Home.md"),
+            metadata,
+            SystemTime::now(),
+        ));
 
-        // Verify we can still get the pages correctly
-        assert!(graph.get_page("Second").is_some());
-        assert!(graph.get_page("Third").is_some());
+        let opts = GraphExportOptions {
+            metadata_keys: vec!["status".to_string()],
+            include_dangling: false,
+        };
+        let dot = graph.export_graph(GraphExportFormat::Dot, &opts);
+        assert!(dot.contains("status=\"draft\""));
     }
 
     #[test]
-    fn test_remove_outgoing_edges() {
+    fn test_export_graph_json_includes_nodes_and_edges() {
         let mut graph = WikiGraph::new();
-        graph.add_page(PageNode::new("Source".to_string(), PathBuf::from("s.md")));
-        graph.add_page(PageNode::new("Target1".to_string(), PathBuf::from("t1.md")));
-        graph.add_page(PageNode::new("Target2".to_string(), PathBuf::from("t2.md")));
-
-        graph.add_link("Source", "Target1", WikiLink::new());
-        graph.add_link("Source", "Target2", WikiLink::new());
+        graph.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
+        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        graph.add_link("Home", "About", WikiLink::new());
 
-        assert_eq!(graph.link_count(), 2);
-        assert_eq!(graph.get_outlinks("Source").len(), 2);
+        let json = graph.export_graph(GraphExportFormat::Json, &GraphExportOptions::default());
+        assert!(json.contains("\"name\": \"Home\""));
+        assert!(json.contains("\"name\": \"About\""));
+        assert!(json.contains("\"from\": \"Home\", \"to\": \"About\""));
+    }
 
-        graph.remove_outgoing_edges("Source");
+    #[test]
+    fn test_export_graph_graphml_includes_nodes_and_edges() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
+        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        graph.add_link("Home", "About", WikiLink::new());
 
-        assert_eq!(graph.link_count(), 0);
-        assert_eq!(graph.get_outlinks("Source").len(), 0);
+        let xml = graph.export_graph(GraphExportFormat::GraphMl, &GraphExportOptions::default());
+        assert!(xml.contains("<node id=\"Home\">"));
+        assert!(xml.contains("<edge source=\"Home\" target=\"About\"/>"));
     }
 
     #[test]
-    fn test_update_page_new() {
+    fn test_export_graph_excludes_dangling_links_by_default() {
         let mut graph = WikiGraph::new();
-
-        let events = graph.update_page(
-            "NewPage",
-            PathBuf::from("NewPage.md"),
+        graph.update_page(
+            "Home",
+            PathBuf::from("Home.md"),
             HashMap::new(),
-            vec![ParsedLink::new("Target".to_string(), None)],
+            vec![ParsedLink::new("Missing".to_string(), None)],
             SystemTime::now(),
         );
 
-        assert!(graph.page_exists("NewPage"));
-        assert!(graph.page_exists("Target")); // Stub created
-        assert_eq!(graph.link_count(), 1);
+        let json = graph.export_graph(GraphExportFormat::Json, &GraphExportOptions::default());
+        assert!(!json.contains("Missing"));
 
-        // Should have one LinkCreated event
-        assert_eq!(events.len(), 1);
-        assert!(matches!(
-            &events[0],
-            GraphEvent::LinkCreated { from, to } if from == "NewPage" && to == "Target"
-        ));
+        let opts = GraphExportOptions {
+            metadata_keys: vec![],
+            include_dangling: true,
+        };
+        let json = graph.export_graph(GraphExportFormat::Json, &opts);
+        assert!(json.contains("\"name\": \"Missing\""));
     }
 
     #[test]
-    fn test_update_page_modify_links() {
+    fn test_profiling_disabled_by_default_records_nothing() {
         let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
+        graph.query(&[]);
+        assert!(graph.profile_snapshot().is_empty());
+    }
 
-        // Create initial page with links to A and B
-        graph.update_page(
-            "Test",
-            PathBuf::from("Test.md"),
-            HashMap::new(),
-            vec![
-                ParsedLink::new("A".to_string(), None),
-                ParsedLink::new("B".to_string(), None),
-            ],
-            SystemTime::now(),
-        );
-
-        assert_eq!(graph.get_outlinks("Test").len(), 2);
-
-        // Update: remove link to A, add link to C
-        let events = graph.update_page(
-            "Test",
-            PathBuf::from("Test.md"),
-            HashMap::new(),
-            vec![
-                ParsedLink::new("B".to_string(), None),
-                ParsedLink::new("C".to_string(), None),
-            ],
-            SystemTime::now(),
-        );
+    #[test]
+    fn test_profiling_records_query_calls_once_enabled() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Home".to_string(), PathBuf::from("Home.md")));
 
-        // Should have LinkRemoved(A) and LinkCreated(C)
-        assert!(events
-            .iter()
-            .any(|e| matches!(e, GraphEvent::LinkRemoved { from, to } if from == "Test" && to == "A")));
-        assert!(events
-            .iter()
-            .any(|e| matches!(e, GraphEvent::LinkCreated { from, to } if from == "Test" && to == "C")));
+        graph.enable_profiling(true);
+        graph.query(&[]);
+        graph.query(&[]);
 
-        // B should not generate events (unchanged)
-        assert!(!events
+        let snapshot = graph.profile_snapshot();
+        let query_entry = snapshot
             .iter()
-            .any(|e| matches!(e, GraphEvent::LinkCreated { to, .. } | GraphEvent::LinkRemoved { to, .. } if to == "B")));
+            .find(|entry| entry.operation == "query")
+            .expect("query operation recorded");
+        assert_eq!(query_entry.count, 2);
     }
 
     #[test]
-    fn test_update_page_no_changes() {
-        let mut graph = WikiGraph::new();
-
-        // Create initial page with link to A
-        graph.update_page(
-            "Test",
-            PathBuf::from("Test.md"),
-            HashMap::new(),
-            vec![ParsedLink::new("A".to_string(), None)],
-            SystemTime::now(),
-        );
+    fn test_profiling_tracks_files_parsed_during_rebuild_incremental() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("A.md"), "# A").unwrap();
 
-        // Update with same links
-        let events = graph.update_page(
-            "Test",
-            PathBuf::from("Test.md"),
-            HashMap::new(),
-            vec![ParsedLink::new("A".to_string(), None)],
-            SystemTime::now(),
-        );
+        let mut graph = WikiGraph::new();
+        graph.enable_profiling(true);
+        graph.rebuild_incremental(temp_dir.path()).unwrap();
 
-        // No link change events
-        assert!(events.is_empty());
+        let snapshot = graph.profile_snapshot();
+        let rebuild_entry = snapshot
+            .iter()
+            .find(|entry| entry.operation == "rebuild")
+            .expect("rebuild operation recorded");
+        assert_eq!(rebuild_entry.items, 1);
     }
 }
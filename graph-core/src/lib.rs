@@ -9,13 +9,24 @@
 //! - `parser` - Markdown parsing for frontmatter and wiki links
 //! - `graph` - WikiGraph implementation using petgraph
 //! - `query` - Filter and MetaTable query support
+//! - `pagespec` - PageSpec query DSL (globs, boolean operators, link selectors)
+//! - `analysis` - Standard graph algorithms (paths, cycles, components)
+//! - `connectivity` - Incremental link-cut-tree connectivity index
+//! - `crdt` - OR-Set CRDT backing WikiGraph::merge for offline editing
 //! - `events` - GraphEvent enum for file watching notifications
+//! - `gitsync` - Translating git change logs into incremental graph updates
 //! - `watcher` - File watching with notify crate
 
+mod analysis;
+mod connectivity;
+mod crdt;
 mod events;
+mod gitsync;
 mod graph;
 mod models;
+mod pagespec;
 mod parser;
+mod profiling;
 mod query;
 mod watcher;
 
@@ -25,13 +36,21 @@ use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 // Re-export for internal use
+pub use analysis::{
+    connected_components, dead_ends, find_cycles, is_cyclic, orphan_pages, shortest_path,
+    strongly_connected_components,
+};
 pub use events::{EventQueue, GraphEvent};
-pub use graph::WikiGraph;
-pub use models::{PageNode, ParsedLink, WikiLink};
-pub use parser::{extract_wiki_links, parse_frontmatter, parse_markdown};
-pub use query::{Filter, MetaTableResult, MetaTableRow, PyFilter};
+pub use gitsync::{parse_whatchanged, ChangedFile};
+pub use graph::{DotOptions, GraphExportFormat, GraphExportOptions, RebuildStats, WikiGraph};
+pub use models::{LinkKind, Name, PageNode, ParsedLink, WikiLink};
+pub use pagespec::{PageSpec, PageSpecError, PageTree};
+pub use parser::{extract_wiki_links, page_analytics, parse_frontmatter, parse_markdown, PageAnalytics};
+pub use query::{FacetBucket, FacetResult, Filter, MetaTableResult, MetaTableRow, PyFilter};
 pub use watcher::{FileWatcher, WatcherHandle};
 
+use profiling::ProfileEntry;
+
 /// Represents a wiki page in the graph.
 ///
 /// This is the Python-facing page info struct that contains
@@ -86,6 +105,47 @@ impl PageInfo {
     }
 }
 
+/// A node in a hierarchical page tree, as returned by `GraphEngine.map()`.
+///
+/// Mirrors [`PageTree`] for Python consumers: `is_real` is false for
+/// intermediate nodes synthesized to keep the tree connected (e.g. `a/b`
+/// when only `a/b/c` matched).
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct PageTreeNode {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub full_name: String,
+    #[pyo3(get)]
+    pub is_real: bool,
+    #[pyo3(get)]
+    pub children: Vec<PageTreeNode>,
+}
+
+#[pymethods]
+impl PageTreeNode {
+    fn __repr__(&self) -> String {
+        format!(
+            "PageTreeNode(full_name='{}', is_real={}, children={})",
+            self.full_name,
+            self.is_real,
+            self.children.len()
+        )
+    }
+}
+
+impl From<PageTree> for PageTreeNode {
+    fn from(tree: PageTree) -> Self {
+        PageTreeNode {
+            name: tree.name,
+            full_name: tree.full_name,
+            is_real: tree.is_real,
+            children: tree.children.into_iter().map(PageTreeNode::from).collect(),
+        }
+    }
+}
+
 impl From<&PageNode> for PageInfo {
     fn from(node: &PageNode) -> Self {
         PageInfo {
@@ -96,6 +156,88 @@ impl From<&PageNode> for PageInfo {
     }
 }
 
+/// Result of `GraphEngine.rebuild_incremental()`: the events produced, and
+/// how many of the scanned files were actually reparsed vs. skipped
+/// because their content hadn't changed, plus how many were removed.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct RebuildReport {
+    #[pyo3(get)]
+    pub events: Vec<GraphEvent>,
+    #[pyo3(get)]
+    pub changed: usize,
+    #[pyo3(get)]
+    pub skipped: usize,
+    #[pyo3(get)]
+    pub removed: usize,
+}
+
+#[pymethods]
+impl RebuildReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "RebuildReport(changed={}, skipped={}, removed={}, events={})",
+            self.changed,
+            self.skipped,
+            self.removed,
+            self.events.len()
+        )
+    }
+}
+
+impl From<(Vec<GraphEvent>, RebuildStats)> for RebuildReport {
+    fn from((events, stats): (Vec<GraphEvent>, RebuildStats)) -> Self {
+        RebuildReport {
+            events,
+            changed: stats.changed,
+            skipped: stats.skipped,
+            removed: stats.removed,
+        }
+    }
+}
+
+/// One row of `GraphEngine.get_profile()`: a named operation's
+/// accumulated call count and timing, from `GraphEngine.enable_profiling`.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct ProfileEntryInfo {
+    #[pyo3(get)]
+    pub operation: String,
+    #[pyo3(get)]
+    pub count: u64,
+    #[pyo3(get)]
+    pub total_ms: f64,
+    #[pyo3(get)]
+    pub mean_ms: f64,
+    #[pyo3(get)]
+    pub max_ms: f64,
+    #[pyo3(get)]
+    pub items: u64,
+}
+
+#[pymethods]
+impl ProfileEntryInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "ProfileEntryInfo(operation={:?}, count={}, total_ms={:.3}, mean_ms={:.3}, max_ms={:.3}, items={})",
+            self.operation, self.count, self.total_ms, self.mean_ms, self.max_ms, self.items
+        )
+    }
+}
+
+impl From<ProfileEntry> for ProfileEntryInfo {
+    fn from(entry: ProfileEntry) -> Self {
+        ProfileEntryInfo {
+            operation: entry.operation,
+            count: entry.count,
+            total_ms: entry.total_ms,
+            mean_ms: entry.mean_ms,
+            max_ms: entry.max_ms,
+            items: entry.items,
+        }
+    }
+}
+
 /// The main graph engine that manages wiki pages and their relationships.
 ///
 /// Provides methods for:
@@ -159,6 +301,20 @@ impl GraphEngine {
         })
     }
 
+    /// Create a GraphEngine identified as replica `replica_id`, for
+    /// reconciling with another engine's graph later via `merge`. Each
+    /// replica being merged together must be constructed with a distinct
+    /// id.
+    #[staticmethod]
+    fn with_replica(data_dir: &str, replica_id: u64) -> PyResult<Self> {
+        Ok(Self {
+            data_dir: PathBuf::from(data_dir),
+            graph: Arc::new(Mutex::new(WikiGraph::new_with_replica(replica_id))),
+            event_queue: EventQueue::new(),
+            watcher_handle: None,
+        })
+    }
+
     /// Get the data directory path.
     ///
     /// Returns the path to the wiki data directory as a string.
@@ -316,6 +472,150 @@ impl GraphEngine {
         Ok(())
     }
 
+    /// Incrementally rebuild the graph by diffing against the filesystem.
+    ///
+    /// Unlike `rebuild()`, this does not clear the graph and reparse every
+    /// file: it only reparses pages whose mtime advanced or that are new,
+    /// and removes pages whose file disappeared. Use this for large wikis
+    /// where a full rescan is wasteful.
+    ///
+    /// Note: If file watching is active, it will be temporarily stopped
+    /// during the sync and restarted after.
+    ///
+    /// # Returns
+    /// The GraphEvents produced by the sync. Raises an exception on IO
+    /// errors.
+    ///
+    /// # Example
+    /// ```python
+    /// engine = GraphEngine("/path/to/wiki")
+    /// engine.rebuild()
+    /// # ... files change on disk ...
+    /// events = engine.sync()
+    /// for event in events:
+    ///     print(event.event_type(), event.page_name())
+    /// ```
+    fn sync(&mut self) -> PyResult<Vec<GraphEvent>> {
+        let was_watching = self.is_watching();
+        if was_watching {
+            self.stop_watching()?;
+        }
+
+        let events = {
+            let mut graph = self.graph.lock().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to acquire graph lock: {}",
+                    e
+                ))
+            })?;
+            graph
+                .sync_from_directory(&self.data_dir)
+                .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?
+        };
+
+        if was_watching {
+            self.start_watching()?;
+        }
+
+        Ok(events)
+    }
+
+    /// Incrementally rebuild the graph using a per-file content hash
+    /// instead of mtime, so files that were touched but not actually
+    /// changed (e.g. after a checkout that resets mtimes) aren't
+    /// needlessly reparsed. Unlike `sync()`, this reads and hashes every
+    /// file whose mtime moved before deciding whether to reparse it, so a
+    /// caller gets an exact changed/skipped count along with the events.
+    ///
+    /// Note: If file watching is active, it will be temporarily stopped
+    /// during the rebuild and restarted after.
+    ///
+    /// # Returns
+    /// A `RebuildReport` with the events produced and the changed/skipped/
+    /// removed file counts. Raises an exception on IO errors.
+    ///
+    /// # Example
+    /// ```python
+    /// engine = GraphEngine("/path/to/wiki")
+    /// engine.rebuild()
+    /// # ... files change on disk ...
+    /// report = engine.rebuild_incremental()
+    /// print(f"{report.changed} changed, {report.skipped} skipped")
+    /// ```
+    fn rebuild_incremental(&mut self) -> PyResult<RebuildReport> {
+        let was_watching = self.is_watching();
+        if was_watching {
+            self.stop_watching()?;
+        }
+
+        let result = {
+            let mut graph = self.graph.lock().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to acquire graph lock: {}",
+                    e
+                ))
+            })?;
+            graph
+                .rebuild_incremental(&self.data_dir)
+                .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?
+        };
+
+        if was_watching {
+            self.start_watching()?;
+        }
+
+        Ok(RebuildReport::from(result))
+    }
+
+    /// Incrementally rebuild from a `git whatchanged`/`git diff --name-status`
+    /// change log, re-parsing only the files it mentions as added or
+    /// modified (and removing pages for deleted ones) instead of
+    /// rescanning the whole data directory.
+    ///
+    /// Note: If file watching is active, it will be temporarily stopped
+    /// during the rebuild and restarted after.
+    ///
+    /// # Arguments
+    /// * `whatchanged_output` - Raw output of `git whatchanged` (or
+    ///   `git diff --name-status <rev>..<rev>`) between two revisions
+    ///
+    /// # Returns
+    /// The GraphEvents produced. Raises an exception on IO errors.
+    ///
+    /// # Example
+    /// ```python
+    /// engine = GraphEngine("/path/to/wiki")
+    /// engine.rebuild()
+    /// log = subprocess.run(["git", "diff", "--name-status", "HEAD~1", "HEAD"],
+    ///                       capture_output=True, text=True, cwd="/path/to/wiki").stdout
+    /// events = engine.rebuild_from_git_log(log)
+    /// ```
+    fn rebuild_from_git_log(&mut self, whatchanged_output: &str) -> PyResult<Vec<GraphEvent>> {
+        let was_watching = self.is_watching();
+        if was_watching {
+            self.stop_watching()?;
+        }
+
+        let changes = parse_whatchanged(whatchanged_output);
+        let events = {
+            let mut graph = self.graph.lock().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to acquire graph lock: {}",
+                    e
+                ))
+            })?;
+            graph
+                .apply_git_changes(&self.data_dir, &changes)
+                .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?
+        };
+
+        if was_watching {
+            self.start_watching()?;
+        }
+
+        Ok(events)
+    }
+
     /// Get metadata for a specific page.
     ///
     /// # Arguments
@@ -369,6 +669,273 @@ impl GraphEngine {
             .collect())
     }
 
+    /// Query pages using a PageSpec expression string.
+    ///
+    /// PageSpec is a small boolean query language: bare terms are glob
+    /// patterns matched against the page name (`*`/`?`), `link(glob)` and
+    /// `backlink(glob)` select by link relationships, `tagged(value)`
+    /// selects by `tags` metadata, and terms combine with `and`/`or`/`not`
+    /// and parentheses.
+    ///
+    /// # Arguments
+    /// * `spec` - A PageSpec expression, e.g. `"blog/* and not tagged(draft)"`
+    ///
+    /// # Returns
+    /// List of PageInfo objects matching the spec. Raises `ValueError` if
+    /// `spec` fails to parse.
+    ///
+    /// # Example
+    /// ```python
+    /// posts = engine.query_pagespec("blog/* and not tagged(draft)")
+    /// ```
+    fn query_pagespec(&self, spec: &str) -> PyResult<Vec<PageInfo>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        let pages = graph
+            .query_pagespec(spec)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(pages.iter().map(|p| PageInfo::from(*p)).collect())
+    }
+
+    /// Select the names of all pages matching a PageSpec expression.
+    ///
+    /// Like `query_pagespec`, but returns just the matching page names
+    /// instead of full `PageInfo` objects. Both accept the same terms,
+    /// including `orphan()` (no inbound links) and `dead_end()` (no
+    /// outbound links).
+    ///
+    /// # Arguments
+    /// * `spec` - A PageSpec expression, e.g. `"orphan() or dead_end()"`
+    ///
+    /// # Returns
+    /// List of matching page names. Raises `ValueError` if `spec` fails to
+    /// parse.
+    ///
+    /// # Example
+    /// ```python
+    /// unreachable = engine.select("link(Target) and not backlink(*)")
+    /// ```
+    fn select(&self, spec: &str) -> PyResult<Vec<String>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        let parsed =
+            PageSpec::parse(spec).map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(graph.select(&parsed))
+    }
+
+    /// Build a hierarchical page tree from a PageSpec expression.
+    ///
+    /// Reproduces ikiwiki/riki's `map` directive: collects matching pages,
+    /// splits their names on `/`, and returns a forest of `PageTreeNode`s
+    /// suitable for rendering a sitemap. Intermediate path segments with no
+    /// matching page of their own are synthesized with `is_real=False`.
+    ///
+    /// # Arguments
+    /// * `spec` - A PageSpec expression, e.g. `"blog/*"`
+    ///
+    /// # Returns
+    /// A forest (list) of root `PageTreeNode`s. Raises `ValueError` if
+    /// `spec` fails to parse.
+    ///
+    /// # Example
+    /// ```python
+    /// roots = engine.map("blog/*")
+    /// for root in roots:
+    ///     print(root.full_name, root.is_real)
+    /// ```
+    fn map(&self, spec: &str) -> PyResult<Vec<PageTreeNode>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        let parsed =
+            PageSpec::parse(spec).map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(graph
+            .map(&parsed)
+            .into_iter()
+            .map(PageTreeNode::from)
+            .collect())
+    }
+
+    /// Aggregate a multi-valued metadata field across pages matching a
+    /// PageSpec, for rendering a tag cloud (riki's `pagestats` directive).
+    ///
+    /// Counts how many matched pages carry each value of `key`, then
+    /// buckets each value's count into one of 10 logarithmic weight
+    /// classes `0..=9` suitable for font-size-style rendering.
+    ///
+    /// # Arguments
+    /// * `spec` - A PageSpec expression, e.g. `"blog/*"`
+    /// * `key` - The metadata field to aggregate (default `"tags"`)
+    ///
+    /// # Returns
+    /// `(value, count, bucket)` tuples, sorted alphabetically by value.
+    /// Raises `ValueError` if `spec` fails to parse.
+    ///
+    /// # Example
+    /// ```python
+    /// for value, count, bucket in engine.pagestats("blog/*"):
+    ///     print(f"{value}: {count} (bucket {bucket})")
+    /// ```
+    #[pyo3(signature = (spec, key="tags".to_string()))]
+    fn pagestats(&self, spec: &str, key: String) -> PyResult<Vec<(String, usize, u8)>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        let parsed =
+            PageSpec::parse(spec).map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(graph.pagestats(&parsed, &key))
+    }
+
+    /// Aggregate a metadata key across every page: for each distinct
+    /// value, how many pages carry it and which ones.
+    ///
+    /// Multi-value fields (e.g. `tags: [a, b, c]`) are exploded, so a page
+    /// with three tags contributes to three buckets.
+    ///
+    /// # Arguments
+    /// * `key` - The metadata field to aggregate (e.g. `"tags"`, `"status"`)
+    ///
+    /// # Returns
+    /// A FacetResult with one bucket per distinct value.
+    ///
+    /// # Example
+    /// ```python
+    /// result = engine.facet("tags")
+    /// for bucket in result.buckets:
+    ///     print(f"{bucket.value}: {bucket.count}")
+    /// ```
+    fn facet(&self, key: &str) -> PyResult<FacetResult> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(graph.facet(key))
+    }
+
+    /// Run `facet` over several metadata keys at once.
+    ///
+    /// # Arguments
+    /// * `keys` - The metadata fields to aggregate
+    ///
+    /// # Returns
+    /// One FacetResult per key, in the same order as `keys`.
+    fn facet_counts(&self, keys: Vec<String>) -> PyResult<Vec<FacetResult>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(graph.facet_counts(&keys))
+    }
+
+    /// Export the whole link graph for use by external visualization tools
+    /// or graph databases.
+    ///
+    /// # Arguments
+    /// * `format` - One of `"dot"`, `"graphml"`, or `"json"` (case-insensitive)
+    /// * `metadata_keys` - Metadata fields to embed as node attributes
+    /// * `include_dangling` - Whether to include edges to pages with no
+    ///   backing file, and the stub nodes themselves
+    ///
+    /// # Returns
+    /// The serialized graph as a string.
+    ///
+    /// # Example
+    /// ```python
+    /// dot = engine.export_graph("dot", metadata_keys=["status"])
+    /// ```
+    #[pyo3(signature = (format, metadata_keys=vec![], include_dangling=false))]
+    fn export_graph(
+        &self,
+        format: &str,
+        metadata_keys: Vec<String>,
+        include_dangling: bool,
+    ) -> PyResult<String> {
+        let format = match format.to_lowercase().as_str() {
+            "dot" => GraphExportFormat::Dot,
+            "graphml" => GraphExportFormat::GraphMl,
+            "json" => GraphExportFormat::Json,
+            other => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "unknown export format '{}': expected one of \"dot\", \"graphml\", \"json\"",
+                    other
+                )))
+            }
+        };
+        let opts = GraphExportOptions {
+            metadata_keys,
+            include_dangling,
+        };
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(graph.export_graph(format, &opts))
+    }
+
+    /// Turn self-profiling of major engine operations on or off.
+    ///
+    /// While enabled, `rebuild()` (as its scan, parse and link-resolution
+    /// steps), `rebuild_incremental()`, and query evaluation record their
+    /// call count and duration; `sync()` is not timed. See
+    /// `get_profile()`. Overhead is near zero once disabled, and disabling
+    /// clears whatever was accumulated.
+    ///
+    /// # Example
+    /// ```python
+    /// engine.enable_profiling(True)
+    /// engine.rebuild()
+    /// for entry in engine.get_profile():
+    ///     print(entry)
+    /// ```
+    fn enable_profiling(&mut self, enabled: bool) -> PyResult<()> {
+        let mut graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        graph.enable_profiling(enabled);
+        Ok(())
+    }
+
+    /// Get the current profiling summary.
+    ///
+    /// # Returns
+    /// One ProfileEntryInfo per operation recorded since profiling was
+    /// last enabled: operation name, call count, total/mean/max duration
+    /// in milliseconds, and (for `rebuild_incremental`) the number of
+    /// files actually reparsed.
+    fn get_profile(&self) -> PyResult<Vec<ProfileEntryInfo>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(graph
+            .profile_snapshot()
+            .into_iter()
+            .map(ProfileEntryInfo::from)
+            .collect())
+    }
+
+    /// List every link that points at a stub page (a link target with no
+    /// backing file), mirroring ikiwiki's `brokenlinks` directive.
+    ///
+    /// # Returns
+    /// `(source page, missing target)` pairs for every broken link.
+    fn broken_links(&self) -> PyResult<Vec<(String, String)>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(graph.broken_links())
+    }
+
+    /// List every real page with no incoming links, mirroring ikiwiki's
+    /// `orphans` directive.
+    ///
+    /// # Returns
+    /// PageInfo objects for all orphaned pages.
+    fn orphans(&self) -> PyResult<Vec<PageInfo>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(graph.orphans().iter().map(|p| PageInfo::from(*p)).collect())
+    }
+
     /// MetaTable query: filter pages and select specific columns.
     ///
     /// Returns a structured result containing only the requested columns
@@ -404,6 +971,257 @@ impl GraphEngine {
         Ok(graph.metatable(&rust_filters, &columns))
     }
 
+    /// MetaTable query with match highlighting.
+    ///
+    /// Like `metatable`, but for each column matched by a `Contains`/`Matches`
+    /// filter, wraps the matched span in `open`/`close` delimiters. Read the
+    /// results via `MetaTableRow.get_highlighted()`/`.highlights`.
+    ///
+    /// # Example
+    /// ```python
+    /// result = engine.metatable_highlighted(
+    ///     [Filter.contains("tags", "rust")],
+    ///     ["name", "tags"],
+    /// )
+    /// for row in result:
+    ///     print(row.get_highlighted("tags"))
+    /// ```
+    #[pyo3(signature = (filters, columns, open="<mark>".to_string(), close="</mark>".to_string()))]
+    fn metatable_highlighted(
+        &self,
+        filters: Vec<PyFilter>,
+        columns: Vec<String>,
+        open: String,
+        close: String,
+    ) -> PyResult<MetaTableResult> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        let rust_filters: Vec<Filter> = filters.iter().map(|f| f.inner.clone()).collect();
+        Ok(graph.metatable_highlighted(&rust_filters, &columns, &open, &close))
+    }
+
+    /// MetaTable query ranked by link-distance proximity to a seed page.
+    ///
+    /// Like `metatable`, but rows are sorted by shortest link-path distance
+    /// to `seed` (nearest first, unreachable pages last). Each row's
+    /// `distance` is populated; passing `k > 1` also populates `path_count`
+    /// with the number of distinct near-shortest paths to that row's page.
+    ///
+    /// # Example
+    /// ```python
+    /// result = engine.metatable_ranked(
+    ///     [Filter.has_key("tags")],
+    ///     ["name"],
+    ///     "HomePage",
+    ///     1,
+    /// )
+    /// for row in result:
+    ///     print(f"{row.page_name}: distance={row.distance}")
+    /// ```
+    #[pyo3(signature = (filters, columns, seed, k=1))]
+    fn metatable_ranked(
+        &self,
+        filters: Vec<PyFilter>,
+        columns: Vec<String>,
+        seed: &str,
+        k: usize,
+    ) -> PyResult<MetaTableResult> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        let rust_filters: Vec<Filter> = filters.iter().map(|f| f.inner.clone()).collect();
+        Ok(graph.metatable_ranked(&rust_filters, &columns, seed, k))
+    }
+
+    /// Render a query result as a GraphViz DOT document.
+    ///
+    /// Runs the same filters/columns as `metatable`, then emits a DOT
+    /// document containing only the matched pages and the links among them.
+    ///
+    /// # Arguments
+    /// * `filters` - List of Filter objects to apply
+    /// * `columns` - List of metadata column names to label each node with
+    /// * `include_neighbors` - Whether to include one hop of unmatched neighbor pages
+    ///
+    /// # Returns
+    /// A DOT document string suitable for piping into `dot`/GraphViz.
+    ///
+    /// # Example
+    /// ```python
+    /// dot = engine.query_dot([Filter.equals("status", "draft")], ["name", "status"])
+    /// ```
+    #[pyo3(signature = (filters, columns, include_neighbors=false))]
+    fn query_dot(
+        &self,
+        filters: Vec<PyFilter>,
+        columns: Vec<String>,
+        include_neighbors: bool,
+    ) -> PyResult<String> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        let rust_filters: Vec<Filter> = filters.iter().map(|f| f.inner.clone()).collect();
+        let result = graph.metatable(&rust_filters, &columns);
+        Ok(graph.to_dot(&result, include_neighbors))
+    }
+
+    /// Render the whole page-link graph as a GraphViz DOT document.
+    ///
+    /// Unlike `query_dot`, which renders a query result, this renders the
+    /// graph itself: every page is a node and every link is an edge, with
+    /// stub pages (link targets with no backing file) drawn with a dashed
+    /// style.
+    ///
+    /// # Arguments
+    /// * `root` - If given, restrict the render to pages reachable from
+    ///   this page within `max_hops` outlink hops
+    /// * `max_hops` - Maximum hop distance from `root` to include (ignored
+    ///   if `root` is `None`)
+    /// * `show_edge_labels` - Whether to label edges with the link's
+    ///   display text, when present
+    ///
+    /// # Returns
+    /// A DOT document string suitable for piping into `dot`/GraphViz.
+    ///
+    /// # Example
+    /// ```python
+    /// dot = engine.graph_dot(root="HomePage", max_hops=2)
+    /// ```
+    #[pyo3(signature = (root=None, max_hops=0, show_edge_labels=false))]
+    fn graph_dot(
+        &self,
+        root: Option<String>,
+        max_hops: usize,
+        show_edge_labels: bool,
+    ) -> PyResult<String> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        let opts = DotOptions {
+            root,
+            max_hops,
+            show_edge_labels,
+        };
+        Ok(graph.to_dot_graph(&opts))
+    }
+
+    /// Find the shortest directed path between two pages, following
+    /// outlinks only.
+    ///
+    /// # Returns
+    /// The path as a list of page names (`from` first, `to` last), or
+    /// `None` if either page doesn't exist or no directed path connects
+    /// them.
+    fn shortest_path(&self, from: &str, to: &str) -> PyResult<Option<Vec<String>>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(shortest_path(&graph, from, to))
+    }
+
+    /// Find every strongly connected component of the link graph
+    /// (Tarjan's algorithm). Stub pages are included as real nodes.
+    ///
+    /// # Returns
+    /// A list of components, each a list of page names.
+    fn strongly_connected_components(&self) -> PyResult<Vec<Vec<String>>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(strongly_connected_components(&graph))
+    }
+
+    /// Whether the link graph contains a cycle (a self-link, or any
+    /// strongly connected component with more than one page).
+    fn is_cyclic(&self) -> PyResult<bool> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(is_cyclic(&graph))
+    }
+
+    /// Find every directed cycle in the link graph.
+    ///
+    /// # Returns
+    /// A list of cycles, each a list of page names. A mutual pair of
+    /// links and a single self-link both count as a cycle.
+    fn find_cycles(&self) -> PyResult<Vec<Vec<String>>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(find_cycles(&graph))
+    }
+
+    /// Names of every page with neither backlinks nor outlinks.
+    ///
+    /// Stricter than `orphans()`, which only requires zero backlinks.
+    fn orphan_pages(&self) -> PyResult<Vec<String>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(orphan_pages(&graph))
+    }
+
+    /// Names of every page with no outgoing links, stub pages included.
+    fn dead_end_pages(&self) -> PyResult<Vec<String>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(dead_ends(&graph))
+    }
+
+    /// Find every connected component of the link graph's undirected
+    /// projection: groups of pages that are mutually reachable once link
+    /// direction is ignored.
+    ///
+    /// # Returns
+    /// A list of components, each a list of page names.
+    fn connected_components(&self) -> PyResult<Vec<Vec<String>>> {
+        let graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(connected_components(&graph))
+    }
+
+    /// Turn on the incremental connectivity index, seeding it from every
+    /// edge currently in the graph. Cheap to call again; only needs to run
+    /// once, typically after the initial directory scan.
+    fn enable_connectivity_index(&self) -> PyResult<()> {
+        let mut graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        graph.enable_connectivity_index();
+        Ok(())
+    }
+
+    /// Whether two pages are connected, ignoring link direction.
+    ///
+    /// # Returns
+    /// `None` if `enable_connectivity_index` hasn't been called yet.
+    fn connected(&self, a: &str, b: &str) -> PyResult<Option<bool>> {
+        let mut graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(graph.connected(a, b))
+    }
+
+    /// Merge another engine's edits into this one's graph, converging
+    /// regardless of merge order — for reconciling two offline copies of
+    /// the wiki. See `WikiGraph::merge`.
+    ///
+    /// # Returns
+    /// The net GraphEvents produced by the merge.
+    fn merge(&self, other: &GraphEngine) -> PyResult<Vec<GraphEvent>> {
+        let other_graph = other.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        let mut graph = self.graph.lock().map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to acquire graph lock: {}", e))
+        })?;
+        Ok(graph.merge(&other_graph))
+    }
+
     // ========== File Watching API ==========
 
     /// Start watching the data directory for changes.
@@ -446,6 +1264,59 @@ impl GraphEngine {
         Ok(())
     }
 
+    /// Start watching the data directory for changes, delivering each
+    /// debounced batch of GraphEvents by calling `callback(events)`
+    /// directly, instead of requiring Python to poll.
+    ///
+    /// A burst of rapid saves (e.g. an editor autosaving) is coalesced
+    /// into a single callback invocation once `debounce_ms` of quiet
+    /// passes; the GIL is acquired only for the duration of the call.
+    ///
+    /// # Arguments
+    /// * `callback` - Called with a `list[GraphEvent]` on the watcher
+    ///   thread for each non-empty batch
+    /// * `debounce_ms` - Coalescing window in milliseconds; defaults to
+    ///   the same 500ms window as `start_watching`
+    ///
+    /// # Example
+    /// ```python
+    /// def on_events(events):
+    ///     for event in events:
+    ///         print(f"{event.event_type()}: {event.page_name()}")
+    ///
+    /// engine.start_watching_with_callback(on_events, debounce_ms=250)
+    /// ```
+    #[pyo3(signature = (callback, debounce_ms=None))]
+    fn start_watching_with_callback(
+        &mut self,
+        callback: Py<PyAny>,
+        debounce_ms: Option<u64>,
+    ) -> PyResult<()> {
+        // Stop existing watcher if any
+        if let Some(mut handle) = self.watcher_handle.take() {
+            handle.stop();
+        }
+
+        let handle = FileWatcher::start_with_callback(
+            self.data_dir.clone(),
+            Arc::clone(&self.graph),
+            debounce_ms.unwrap_or(watcher::DEBOUNCE_DURATION_MS),
+            move |events: Vec<GraphEvent>| {
+                Python::with_gil(|py| {
+                    if let Err(e) = callback.call1(py, (events,)) {
+                        e.print(py);
+                    }
+                });
+            },
+        )
+        .map_err(|e| {
+            pyo3::exceptions::PyIOError::new_err(format!("Failed to start file watcher: {}", e))
+        })?;
+
+        self.watcher_handle = Some(handle);
+        Ok(())
+    }
+
     /// Stop watching for file changes.
     ///
     /// This stops the background watcher thread. Any unpolled events
@@ -513,10 +1384,15 @@ impl GraphEngine {
 fn graph_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<GraphEngine>()?;
     m.add_class::<PageInfo>()?;
+    m.add_class::<PageTreeNode>()?;
     m.add_class::<PyFilter>()?;
     m.add_class::<MetaTableResult>()?;
     m.add_class::<MetaTableRow>()?;
+    m.add_class::<FacetResult>()?;
+    m.add_class::<FacetBucket>()?;
     m.add_class::<GraphEvent>()?;
+    m.add_class::<RebuildReport>()?;
+    m.add_class::<ProfileEntryInfo>()?;
 
     // Add module-level version info
     m.add("__version__", "0.1.0")?;
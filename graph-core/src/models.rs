@@ -25,6 +25,30 @@ pub struct PageNode {
 
     /// Last modification time of the file
     pub last_modified: SystemTime,
+
+    /// Whether this node is a stub: created to stand in for a link target
+    /// that has no backing file yet, rather than a real page parsed from
+    /// disk. See [`PageNode::stub`].
+    pub is_stub: bool,
+
+    /// Stable content hash of the file's raw bytes, if one has been
+    /// computed. Used by [`crate::graph::WikiGraph::rebuild_incremental`]
+    /// to tell whether a file actually changed, instead of trusting mtime
+    /// alone. `None` for stubs and for pages built without going through
+    /// that path.
+    pub content_hash: Option<String>,
+
+    /// The links this page's content contains, as parsed, before they're
+    /// resolved against the graph. Kept alongside the resolved edges in
+    /// [`crate::graph::WikiGraph`]'s petgraph storage so a page can report
+    /// what it links to without a graph lookup. Empty for stubs.
+    pub outbound_links: Vec<ParsedLink>,
+
+    /// Non-`.md` files discovered beside this page's source file (same
+    /// directory, or a same-named sibling directory), relative to the
+    /// wiki data directory like `file_path`. Empty for stubs. See
+    /// [`crate::graph::WikiGraph::build_from_directory`].
+    pub assets: Vec<PathBuf>,
 }
 
 impl PageNode {
@@ -37,6 +61,10 @@ impl PageNode {
             file_path,
             metadata: HashMap::new(),
             last_modified: SystemTime::now(),
+            is_stub: false,
+            content_hash: None,
+            outbound_links: Vec::new(),
+            assets: Vec::new(),
         }
     }
 
@@ -52,8 +80,109 @@ impl PageNode {
             file_path,
             metadata,
             last_modified,
+            is_stub: false,
+            content_hash: None,
+            outbound_links: Vec::new(),
+            assets: Vec::new(),
+        }
+    }
+
+    /// Create a stub PageNode standing in for a link target with no
+    /// backing file on disk. Stubs carry no metadata and are excluded from
+    /// [`crate::graph::WikiGraph::orphans`]; every edge pointing at one is
+    /// reported by [`crate::graph::WikiGraph::broken_links`].
+    pub fn stub(name: String, file_path: PathBuf) -> Self {
+        Self {
+            name,
+            file_path,
+            metadata: HashMap::new(),
+            last_modified: SystemTime::now(),
+            is_stub: true,
+            content_hash: None,
+            outbound_links: Vec::new(),
+            assets: Vec::new(),
         }
     }
+
+    /// The links this page's content contains, as parsed.
+    pub fn outbound_links(&self) -> &[ParsedLink] {
+        &self.outbound_links
+    }
+
+    /// This page's identity as a [`Name`], derived from `file_path`.
+    ///
+    /// `name` and `file_path` conflate the logical page name with the
+    /// source path; `Name` additionally centralizes the destination
+    /// (rendered output) path and the bare leaf name, so callers that
+    /// need those don't have to re-derive them ad hoc.
+    pub fn page_identity(&self) -> Name {
+        Name::from_relative_path(&self.file_path)
+    }
+}
+
+/// A page's identity, independent of where it happens to live on disk.
+///
+/// [`PageNode::name`] and [`PageNode::file_path`] conflate three distinct
+/// concerns: the logical page name used for link resolution, the source
+/// file's path, and the eventual rendered output path. `Name` centralizes
+/// the derivation of the latter two (and the bare leaf name) from a
+/// source path in one tested place, following the ikiwiki/riki practice
+/// of deriving all of a page's path-shaped properties together instead
+/// of re-deriving them piecemeal at each call site.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Name {
+    /// The source file's path, relative to the wiki data directory.
+    pub source_path: PathBuf,
+
+    /// Where this page renders to, relative to the output root:
+    /// `source_path` with its extension replaced by `.html`.
+    pub dest_path: PathBuf,
+
+    /// The hierarchical page path used for link resolution, e.g.
+    /// `"blog/intro"` for `blog/intro.md`. Matches [`PageNode::name`].
+    pub page: String,
+
+    /// The final component of `page`, e.g. `"intro"` for `"blog/intro"`.
+    pub page_name: String,
+}
+
+impl Name {
+    /// Derive a `Name` from a source path relative to the wiki data
+    /// directory, e.g. `"blog/intro.md"`.
+    pub fn from_relative_path(relative: &std::path::Path) -> Self {
+        let page = crate::graph::name_from_relative_path(relative);
+        let page_name = page
+            .rsplit('/')
+            .next()
+            .unwrap_or(page.as_str())
+            .to_string();
+        let dest_path = relative.with_extension("html");
+        Self {
+            source_path: relative.to_path_buf(),
+            dest_path,
+            page,
+            page_name,
+        }
+    }
+}
+
+impl From<&PageNode> for Name {
+    fn from(node: &PageNode) -> Self {
+        Name::from_relative_path(&node.file_path)
+    }
+}
+
+/// Distinguishes a navigational `[[Page]]` link from an `![[Page]]`
+/// transclusion, so graph consumers can tell "links to" from "embeds
+/// content from" apart on the same edge type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LinkKind {
+    /// An ordinary `[[Page]]` link.
+    #[default]
+    Navigational,
+    /// An `![[Page]]` transclusion, per
+    /// [`crate::parser::ParsedPage::embeds`].
+    Transclusion,
 }
 
 /// Represents a wiki link (edge) in the graph.
@@ -65,18 +194,34 @@ pub struct WikiLink {
     /// Optional display text for the link.
     /// None means the link is displayed as the page name itself.
     pub display_text: Option<String>,
+
+    /// Whether this edge represents a navigational link or a
+    /// transclusion. Defaults to [`LinkKind::Navigational`].
+    pub kind: LinkKind,
 }
 
 impl WikiLink {
     /// Create a new WikiLink without display text.
     pub fn new() -> Self {
-        Self { display_text: None }
+        Self {
+            display_text: None,
+            kind: LinkKind::Navigational,
+        }
     }
 
     /// Create a new WikiLink with display text.
     pub fn with_display_text(text: String) -> Self {
         Self {
             display_text: Some(text),
+            kind: LinkKind::Navigational,
+        }
+    }
+
+    /// Create a new WikiLink representing an `![[Page]]` transclusion.
+    pub fn transclusion(display_text: Option<String>) -> Self {
+        Self {
+            display_text,
+            kind: LinkKind::Transclusion,
         }
     }
 }
@@ -86,19 +231,34 @@ impl WikiLink {
 /// This is used during parsing to collect links before adding them to the graph.
 #[derive(Clone, Debug, PartialEq)]
 pub struct ParsedLink {
-    /// The target page name
+    /// The target page name, preserving any `/`-separated namespace path.
+    /// Empty for a bare `[[#Section]]` intra-page link.
     pub target: String,
 
     /// Optional display text
     pub display_text: Option<String>,
+
+    /// The portion of the link after a `#`, if any, e.g. `Section` in
+    /// `[[Page#Section]]` or `[[#Section]]`.
+    pub anchor: Option<String>,
 }
 
 impl ParsedLink {
-    /// Create a new ParsedLink.
+    /// Create a new ParsedLink with no section anchor.
     pub fn new(target: String, display_text: Option<String>) -> Self {
         Self {
             target,
             display_text,
+            anchor: None,
+        }
+    }
+
+    /// Create a new ParsedLink with a section anchor.
+    pub fn with_anchor(target: String, display_text: Option<String>, anchor: Option<String>) -> Self {
+        Self {
+            target,
+            display_text,
+            anchor,
         }
     }
 }
@@ -142,6 +302,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_page_node_new_is_not_stub() {
+        let node = PageNode::new("TestPage".to_string(), PathBuf::from("TestPage.md"));
+        assert!(!node.is_stub);
+    }
+
+    #[test]
+    fn test_page_node_stub() {
+        let node = PageNode::stub("Missing".to_string(), PathBuf::from("Missing.md"));
+        assert!(node.is_stub);
+        assert!(node.metadata.is_empty());
+    }
+
+    #[test]
+    fn test_page_node_content_hash_defaults_to_none() {
+        let node = PageNode::new("TestPage".to_string(), PathBuf::from("TestPage.md"));
+        assert_eq!(node.content_hash, None);
+    }
+
+    #[test]
+    fn test_page_node_assets_defaults_to_empty() {
+        let node = PageNode::new("TestPage".to_string(), PathBuf::from("TestPage.md"));
+        assert!(node.assets.is_empty());
+    }
+
+    #[test]
+    fn test_name_from_relative_path_splits_page_and_dest() {
+        let name = Name::from_relative_path(&PathBuf::from("blog/intro.md"));
+        assert_eq!(name.source_path, PathBuf::from("blog/intro.md"));
+        assert_eq!(name.dest_path, PathBuf::from("blog/intro.html"));
+        assert_eq!(name.page, "blog/intro");
+        assert_eq!(name.page_name, "intro");
+    }
+
+    #[test]
+    fn test_name_from_relative_path_top_level() {
+        let name = Name::from_relative_path(&PathBuf::from("FAQ.md"));
+        assert_eq!(name.page, "FAQ");
+        assert_eq!(name.page_name, "FAQ");
+    }
+
+    #[test]
+    fn test_page_node_page_identity_matches_name_and_file_path() {
+        let node = PageNode::new("blog/intro".to_string(), PathBuf::from("blog/intro.md"));
+        let identity = node.page_identity();
+        assert_eq!(identity.page, node.name);
+        assert_eq!(identity.source_path, node.file_path);
+    }
+
     #[test]
     fn test_wiki_link_new() {
         let link = WikiLink::new();
@@ -152,6 +361,19 @@ mod tests {
     fn test_wiki_link_with_display_text() {
         let link = WikiLink::with_display_text("Custom Text".to_string());
         assert_eq!(link.display_text, Some("Custom Text".to_string()));
+        assert_eq!(link.kind, LinkKind::Navigational);
+    }
+
+    #[test]
+    fn test_wiki_link_new_defaults_to_navigational() {
+        let link = WikiLink::new();
+        assert_eq!(link.kind, LinkKind::Navigational);
+    }
+
+    #[test]
+    fn test_wiki_link_transclusion_is_marked_as_such() {
+        let link = WikiLink::transclusion(None);
+        assert_eq!(link.kind, LinkKind::Transclusion);
     }
 
     #[test]
@@ -159,5 +381,17 @@ mod tests {
         let link = ParsedLink::new("TargetPage".to_string(), Some("Display".to_string()));
         assert_eq!(link.target, "TargetPage");
         assert_eq!(link.display_text, Some("Display".to_string()));
+        assert_eq!(link.anchor, None);
+    }
+
+    #[test]
+    fn test_parsed_link_with_anchor() {
+        let link = ParsedLink::with_anchor(
+            "TargetPage".to_string(),
+            None,
+            Some("Section".to_string()),
+        );
+        assert_eq!(link.target, "TargetPage");
+        assert_eq!(link.anchor, Some("Section".to_string()));
     }
 }
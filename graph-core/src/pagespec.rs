@@ -0,0 +1,555 @@
+//! PageSpec query DSL, modeled on ikiwiki/riki's `PageSpec`.
+//!
+//! A `PageSpec` is a small boolean expression language for selecting pages
+//! by name and by link relationships:
+//!
+//! - Bare terms are glob patterns matched against the page name (`*` matches
+//!   any run of characters, `?` matches exactly one).
+//! - `link(glob)` selects pages whose outlinks include a page matching `glob`.
+//! - `backlink(glob)` selects pages whose backlinks include a page matching `glob`.
+//! - `tagged(value)` selects pages whose `tags` metadata contains `value`.
+//! - `orphan()` selects pages with no incoming links; `dead_end()` selects
+//!   pages with no outgoing links.
+//!
+//! `link`/`backlink`/`orphan`/`dead_end` all consider navigational links
+//! only — an `![[Embed]]` transclusion edge doesn't count as a link for
+//! any of them.
+//! - Terms combine with `and`, `or`, `not`, and parentheses, with standard
+//!   precedence: `not` binds tighter than `and`, which binds tighter than `or`.
+//!
+//! An empty spec matches nothing.
+
+use crate::graph::WikiGraph;
+use crate::models::PageNode;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A parsed page-specification expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PageSpec {
+    /// A glob pattern matched against the page name.
+    Glob(String),
+    /// Matches pages whose outlinks include a page matching the glob.
+    Link(String),
+    /// Matches pages whose backlinks include a page matching the glob.
+    Backlink(String),
+    /// Matches pages whose `tags` metadata contains the given value.
+    Tagged(String),
+    /// Matches pages with no incoming links.
+    Orphan,
+    /// Matches pages with no outgoing links.
+    DeadEnd,
+    And(Box<PageSpec>, Box<PageSpec>),
+    Or(Box<PageSpec>, Box<PageSpec>),
+    Not(Box<PageSpec>),
+    /// An empty spec, which matches nothing.
+    Empty,
+}
+
+/// Error parsing a PageSpec expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageSpecError(pub String);
+
+impl fmt::Display for PageSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid PageSpec: {}", self.0)
+    }
+}
+
+impl std::error::Error for PageSpecError {}
+
+impl PageSpec {
+    /// Parse a PageSpec expression.
+    ///
+    /// An empty or all-whitespace input parses to `PageSpec::Empty`, which
+    /// matches nothing.
+    pub fn parse(input: &str) -> Result<PageSpec, PageSpecError> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Ok(PageSpec::Empty);
+        }
+
+        let tokens = tokenize(trimmed);
+        let mut parser = Parser { tokens: &tokens, pos: 0 };
+        let spec = parser.parse_or()?;
+
+        if parser.pos != tokens.len() {
+            return Err(PageSpecError(format!(
+                "unexpected trailing input near token {}",
+                parser.pos
+            )));
+        }
+
+        Ok(spec)
+    }
+
+    /// Check whether `page` matches this spec.
+    ///
+    /// Link-based terms (`link`, `backlink`) require `graph` to resolve
+    /// outlinks/backlinks.
+    pub fn matches(&self, page: &PageNode, graph: &WikiGraph) -> bool {
+        match self {
+            PageSpec::Glob(pattern) => glob_match(pattern, &page.name),
+            PageSpec::Link(pattern) => graph
+                .get_navigational_outlinks(&page.name)
+                .iter()
+                .any(|target| glob_match(pattern, target)),
+            PageSpec::Backlink(pattern) => graph
+                .get_navigational_backlinks(&page.name)
+                .iter()
+                .any(|source| glob_match(pattern, source)),
+            PageSpec::Tagged(value) => page
+                .metadata
+                .get("tags")
+                .map(|tags| tags.contains(value))
+                .unwrap_or(false),
+            PageSpec::Orphan => graph.get_navigational_backlinks(&page.name).is_empty(),
+            PageSpec::DeadEnd => graph.get_navigational_outlinks(&page.name).is_empty(),
+            PageSpec::And(a, b) => a.matches(page, graph) && b.matches(page, graph),
+            PageSpec::Or(a, b) => a.matches(page, graph) || b.matches(page, graph),
+            PageSpec::Not(a) => !a.matches(page, graph),
+            PageSpec::Empty => false,
+        }
+    }
+}
+
+/// Match a glob pattern (`*` = any run of characters, `?` = exactly one)
+/// against the whole of `text`.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn helper(p: &[char], t: &[char]) -> bool {
+        match (p.first(), t.first()) {
+            (None, None) => true,
+            (Some('*'), _) => helper(&p[1..], t) || (!t.is_empty() && helper(p, &t[1..])),
+            (Some('?'), Some(_)) => helper(&p[1..], &t[1..]),
+            (Some(pc), Some(tc)) if pc == tc => helper(&p[1..], &t[1..]),
+            _ => false,
+        }
+    }
+
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    helper(&p, &t)
+}
+
+/// A node in the hierarchical page tree produced by
+/// [`crate::graph::WikiGraph::map`], reproducing ikiwiki/riki's `map`
+/// directive.
+///
+/// `name` is this node's path segment (not the full page name); `full_name`
+/// is the `/`-joined path from the forest root. When a matching page like
+/// `a/b/c` has no matching ancestor `a/b`, an intermediate node is
+/// synthesized with `is_real: false` so renderers can distinguish it from
+/// an actual page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageTree {
+    pub name: String,
+    pub full_name: String,
+    pub is_real: bool,
+    pub children: Vec<PageTree>,
+}
+
+/// Build a [`PageTree`] forest from a set of matched page names, splitting
+/// each name on `/` and synthesizing intermediate parents as needed.
+/// Sibling order is alphabetical at every level.
+#[derive(Default)]
+struct TreeBuilder {
+    children: BTreeMap<String, TreeBuilder>,
+    is_real: bool,
+}
+
+impl TreeBuilder {
+    fn insert(&mut self, segments: &[&str]) {
+        if segments.is_empty() {
+            return;
+        }
+        let child = self.children.entry(segments[0].to_string()).or_default();
+        if segments.len() == 1 {
+            child.is_real = true;
+        } else {
+            child.insert(&segments[1..]);
+        }
+    }
+
+    fn into_trees(self, prefix: &str) -> Vec<PageTree> {
+        self.children
+            .into_iter()
+            .map(|(name, node)| {
+                let full_name = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", prefix, name)
+                };
+                let children = node.into_trees(&full_name);
+                PageTree {
+                    name,
+                    full_name,
+                    is_real: node.is_real,
+                    children,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Build a [`PageTree`] forest from a sorted, deduplicated set of matching
+/// page names (see [`crate::graph::WikiGraph::map`]).
+pub(crate) fn build_page_tree(mut names: Vec<String>) -> Vec<PageTree> {
+    names.sort();
+    names.dedup();
+
+    let mut builder = TreeBuilder::default();
+    for name in &names {
+        let segments: Vec<&str> = name.split('/').collect();
+        builder.insert(&segments);
+    }
+
+    builder.into_trees("")
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Word(String),
+}
+
+/// Split `input` into tokens. Parenthesization and whitespace delimit
+/// words; `and`/`or`/`not` (case-insensitive) are recognized as keywords.
+fn tokenize(input: &str) -> Vec<Token> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+            continue;
+        }
+        if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '(' && chars[i] != ')' {
+            i += 1;
+        }
+        let word: String = chars[start..i].iter().collect();
+        match word.to_ascii_lowercase().as_str() {
+            "and" => tokens.push(Token::And),
+            "or" => tokens.push(Token::Or),
+            "not" => tokens.push(Token::Not),
+            _ => tokens.push(Token::Word(word)),
+        }
+    }
+
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<&Token, PageSpecError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or_else(|| PageSpecError("unexpected end of input".to_string()))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), PageSpecError> {
+        let token = self.next()?;
+        if *token == expected {
+            Ok(())
+        } else {
+            Err(PageSpecError(format!(
+                "expected {:?}, found {:?}",
+                expected, token
+            )))
+        }
+    }
+
+    // or := and ("or" and)*
+    fn parse_or(&mut self) -> Result<PageSpec, PageSpecError> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            let right = self.parse_and()?;
+            left = PageSpec::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    // and := not ("and" not)*
+    fn parse_and(&mut self) -> Result<PageSpec, PageSpecError> {
+        let mut left = self.parse_not()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            let right = self.parse_not()?;
+            left = PageSpec::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    // not := "not" not | atom
+    fn parse_not(&mut self) -> Result<PageSpec, PageSpecError> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            let inner = self.parse_not()?;
+            return Ok(PageSpec::Not(Box::new(inner)));
+        }
+        self.parse_atom()
+    }
+
+    // atom := "(" or ")" | word | word "(" word ")"
+    fn parse_atom(&mut self) -> Result<PageSpec, PageSpecError> {
+        match self.next()?.clone() {
+            Token::LParen => {
+                let inner = self.parse_or()?;
+                self.expect(Token::RParen)?;
+                Ok(inner)
+            }
+            Token::Word(word) => {
+                if self.peek() == Some(&Token::LParen) {
+                    self.pos += 1; // consume '('
+
+                    if self.peek() == Some(&Token::RParen) {
+                        self.pos += 1; // consume ')'
+                        return match word.to_ascii_lowercase().as_str() {
+                            "orphan" => Ok(PageSpec::Orphan),
+                            "dead_end" => Ok(PageSpec::DeadEnd),
+                            other => Err(PageSpecError(format!("unknown function '{}'", other))),
+                        };
+                    }
+
+                    let arg = match self.next()?.clone() {
+                        Token::Word(arg) => arg,
+                        other => {
+                            return Err(PageSpecError(format!(
+                                "expected function argument, found {:?}",
+                                other
+                            )))
+                        }
+                    };
+                    self.expect(Token::RParen)?;
+
+                    match word.to_ascii_lowercase().as_str() {
+                        "link" => Ok(PageSpec::Link(arg)),
+                        "backlink" => Ok(PageSpec::Backlink(arg)),
+                        "tagged" => Ok(PageSpec::Tagged(arg)),
+                        other => Err(PageSpecError(format!("unknown function '{}'", other))),
+                    }
+                } else {
+                    Ok(PageSpec::Glob(word))
+                }
+            }
+            other => Err(PageSpecError(format!("unexpected token {:?}", other))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WikiLink;
+    use std::path::PathBuf;
+
+    fn page(name: &str, tags: Vec<&str>) -> PageNode {
+        let mut metadata = std::collections::HashMap::new();
+        if !tags.is_empty() {
+            metadata.insert("tags".to_string(), tags.iter().map(|s| s.to_string()).collect());
+        }
+        PageNode::with_metadata(
+            name.to_string(),
+            PathBuf::from(format!("{}.md", name)),
+            metadata,
+            std::time::SystemTime::now(),
+        )
+    }
+
+    #[test]
+    fn test_empty_spec_matches_nothing() {
+        let spec = PageSpec::parse("").unwrap();
+        let graph = WikiGraph::new();
+        assert!(!spec.matches(&page("Anything", vec![]), &graph));
+    }
+
+    #[test]
+    fn test_glob_exact() {
+        let spec = PageSpec::parse("HomePage").unwrap();
+        let graph = WikiGraph::new();
+        assert!(spec.matches(&page("HomePage", vec![]), &graph));
+        assert!(!spec.matches(&page("Other", vec![]), &graph));
+    }
+
+    #[test]
+    fn test_glob_star() {
+        let spec = PageSpec::parse("blog/*").unwrap();
+        let graph = WikiGraph::new();
+        assert!(spec.matches(&page("blog/post1", vec![]), &graph));
+        assert!(!spec.matches(&page("docs/post1", vec![]), &graph));
+    }
+
+    #[test]
+    fn test_glob_question_mark() {
+        let spec = PageSpec::parse("v?").unwrap();
+        let graph = WikiGraph::new();
+        assert!(spec.matches(&page("v1", vec![]), &graph));
+        assert!(!spec.matches(&page("v10", vec![]), &graph));
+    }
+
+    #[test]
+    fn test_tagged() {
+        let spec = PageSpec::parse("tagged(rust)").unwrap();
+        let graph = WikiGraph::new();
+        assert!(spec.matches(&page("Post", vec!["rust", "wiki"]), &graph));
+        assert!(!spec.matches(&page("Post", vec!["python"]), &graph));
+    }
+
+    #[test]
+    fn test_link_and_backlink() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("Index", vec![]));
+        graph.add_page(page("About", vec![]));
+        graph.add_link("Index", "About", WikiLink::new());
+
+        let link_spec = PageSpec::parse("link(About)").unwrap();
+        assert!(link_spec.matches(graph.get_page("Index").unwrap(), &graph));
+        assert!(!link_spec.matches(graph.get_page("About").unwrap(), &graph));
+
+        let backlink_spec = PageSpec::parse("backlink(Index)").unwrap();
+        assert!(backlink_spec.matches(graph.get_page("About").unwrap(), &graph));
+    }
+
+    #[test]
+    fn test_and_or_not_precedence() {
+        // not tagged(draft) and tagged(rust) or blog/*
+        // parses as: (not tagged(draft) and tagged(rust)) or blog/*
+        let spec = PageSpec::parse("not tagged(draft) and tagged(rust) or blog/*").unwrap();
+        let graph = WikiGraph::new();
+
+        assert!(spec.matches(&page("Post", vec!["rust"]), &graph));
+        assert!(!spec.matches(&page("Post", vec!["draft", "rust"]), &graph));
+        assert!(spec.matches(&page("blog/anything", vec!["draft"]), &graph));
+    }
+
+    #[test]
+    fn test_parens_override_precedence() {
+        let spec = PageSpec::parse("not (tagged(draft) and tagged(rust))").unwrap();
+        let graph = WikiGraph::new();
+
+        assert!(!spec.matches(&page("Post", vec!["draft", "rust"]), &graph));
+        assert!(spec.matches(&page("Post", vec!["rust"]), &graph));
+    }
+
+    #[test]
+    fn test_unknown_function_errors() {
+        assert!(PageSpec::parse("bogus(x)").is_err());
+    }
+
+    #[test]
+    fn test_orphan_and_dead_end() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("Index", vec![]));
+        graph.add_page(page("About", vec![]));
+        graph.add_link("Index", "About", WikiLink::new());
+
+        let orphan_spec = PageSpec::parse("orphan()").unwrap();
+        assert!(orphan_spec.matches(graph.get_page("Index").unwrap(), &graph));
+        assert!(!orphan_spec.matches(graph.get_page("About").unwrap(), &graph));
+
+        let dead_end_spec = PageSpec::parse("dead_end()").unwrap();
+        assert!(!dead_end_spec.matches(graph.get_page("Index").unwrap(), &graph));
+        assert!(dead_end_spec.matches(graph.get_page("About").unwrap(), &graph));
+    }
+
+    #[test]
+    fn test_link_backlink_orphan_dead_end_ignore_transclusion_edges() {
+        let mut graph = WikiGraph::new();
+        graph.add_page(page("Index", vec![]));
+        graph.add_page(page("About", vec![]));
+        graph.add_link("Index", "About", WikiLink::transclusion(None));
+
+        // An embed-only edge doesn't count as a link/backlink...
+        let link_spec = PageSpec::parse("link(About)").unwrap();
+        assert!(!link_spec.matches(graph.get_page("Index").unwrap(), &graph));
+        let backlink_spec = PageSpec::parse("backlink(Index)").unwrap();
+        assert!(!backlink_spec.matches(graph.get_page("About").unwrap(), &graph));
+
+        // ...so Index is still dead_end() and About is still orphan().
+        let orphan_spec = PageSpec::parse("orphan()").unwrap();
+        assert!(orphan_spec.matches(graph.get_page("About").unwrap(), &graph));
+        let dead_end_spec = PageSpec::parse("dead_end()").unwrap();
+        assert!(dead_end_spec.matches(graph.get_page("Index").unwrap(), &graph));
+    }
+
+    #[test]
+    fn test_unknown_nullary_function_errors() {
+        assert!(PageSpec::parse("bogus()").is_err());
+    }
+
+    #[test]
+    fn test_unbalanced_parens_errors() {
+        assert!(PageSpec::parse("(tagged(rust)").is_err());
+    }
+
+    #[test]
+    fn test_build_page_tree_flat() {
+        let trees = build_page_tree(vec!["B".to_string(), "A".to_string()]);
+        let names: Vec<&str> = trees.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["A", "B"]);
+        assert!(trees.iter().all(|t| t.is_real && t.children.is_empty()));
+    }
+
+    #[test]
+    fn test_build_page_tree_synthesizes_missing_parent() {
+        let trees = build_page_tree(vec!["a/b/c".to_string()]);
+        assert_eq!(trees.len(), 1);
+
+        let a = &trees[0];
+        assert_eq!(a.name, "a");
+        assert_eq!(a.full_name, "a");
+        assert!(!a.is_real);
+
+        let b = &a.children[0];
+        assert_eq!(b.full_name, "a/b");
+        assert!(!b.is_real);
+
+        let c = &b.children[0];
+        assert_eq!(c.full_name, "a/b/c");
+        assert!(c.is_real);
+    }
+
+    #[test]
+    fn test_build_page_tree_marks_real_ancestor() {
+        let trees = build_page_tree(vec!["a".to_string(), "a/b".to_string()]);
+        assert_eq!(trees.len(), 1);
+        assert!(trees[0].is_real);
+        assert_eq!(trees[0].children.len(), 1);
+        assert!(trees[0].children[0].is_real);
+    }
+
+    #[test]
+    fn test_build_page_tree_dedupes() {
+        let trees = build_page_tree(vec!["a".to_string(), "a".to_string()]);
+        assert_eq!(trees.len(), 1);
+    }
+}
@@ -1,10 +1,20 @@
 //! Markdown parser for wiki link and frontmatter extraction.
 //!
 //! This module provides functions to parse markdown files and extract:
-//! - YAML frontmatter (metadata between `---` markers)
+//! - Frontmatter (metadata at the start of the file, in YAML between
+//!   `---` markers, TOML between `+++` markers, JSON in a leading
+//!   `{...}` block, or `key: value` lines in a leading
+//!   `<!-- ... -->` HTML comment)
 //! - Wiki links (`[[PageName]]` and `[[PageName|Display Text]]` syntax)
+//! - The page title and heading anchors, for TOC tooling
+//! - Shortcode directives (`{{ name(arg="value") }}` and
+//!   `{% name %} ... {% end %}`), as a hook point for later rendering
+//! - A stable page id and `created`/`modified` timestamps. The id would
+//!   give pages a durable identity across renames, but nothing
+//!   downstream reads it yet — see [`ParsedPage::id`]
 
 use crate::models::ParsedLink;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
 /// Result of parsing a markdown file.
@@ -18,12 +28,283 @@ pub struct ParsedPage {
 
     /// The content without frontmatter (markdown body)
     pub content: String,
+
+    /// The page title: the body's first level-1 ATX heading (`# Text`),
+    /// falling back to a `title` frontmatter key if there's no H1.
+    pub title: Option<String>,
+
+    /// Every ATX heading (`#` through `######`) found in the body, each
+    /// with a GitHub-style slug anchor, in document order.
+    pub headings: Vec<Heading>,
+
+    /// Obsidian-style transclusions (`![[Page]]`, `![[Page#Section]]`)
+    /// found in the body. Kept separate from `links` since a transclusion
+    /// means "inline this page's content here", a different graph edge
+    /// than a navigational link.
+    pub embeds: Vec<ParsedLink>,
+
+    /// Shortcode directives found in the body, in document order.
+    pub shortcodes: Vec<Shortcode>,
+
+    /// A stable page identifier: the `page-id` metadata key if present,
+    /// otherwise a hex-encoded SHA-256 hash of the content body. This
+    /// would stay stable across a rename the way `name`/`file_path`
+    /// don't, but it's parse-time only for now — nothing downstream
+    /// (`PageNode`, `WikiGraph::merge`, rename detection) reads it yet,
+    /// so it doesn't actually provide durable identity for graph edges
+    /// today.
+    pub id: String,
+
+    /// The `created` metadata key, parsed as an RFC 3339 timestamp.
+    pub created: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// The `modified` metadata key, parsed as an RFC 3339 timestamp.
+    pub modified: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Word count and estimated reading time for the body. See
+    /// [`page_analytics`].
+    pub analytics: PageAnalytics,
+}
+
+/// Word count and estimated reading time for a page, the way Zola computes
+/// reading analytics per page. See [`page_analytics`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PageAnalytics {
+    /// Whitespace-separated word count of the body, with wiki-link markup
+    /// (`[[Page|Display Text]]`) reduced to its display text, or its
+    /// target if there is none.
+    pub word_count: usize,
+
+    /// Estimated reading time in whole minutes at [`WORDS_PER_MINUTE`]
+    /// words per minute, rounded up so a short page never reads as "0
+    /// minutes".
+    pub reading_time_minutes: usize,
+}
+
+/// Default reading speed used to estimate [`PageAnalytics::reading_time_minutes`].
+pub const WORDS_PER_MINUTE: usize = 200;
+
+/// Compute word count and reading time for a page body (frontmatter
+/// already stripped, e.g. [`ParsedPage::content`]) at the default
+/// [`WORDS_PER_MINUTE`] reading speed.
+pub fn page_analytics(content: &str) -> PageAnalytics {
+    page_analytics_with_speed(content, WORDS_PER_MINUTE)
+}
+
+/// Like [`page_analytics`], with a configurable words-per-minute reading
+/// speed instead of the [`WORDS_PER_MINUTE`] default.
+pub fn page_analytics_with_speed(content: &str, words_per_minute: usize) -> PageAnalytics {
+    let rendered = strip_wiki_link_markup(content);
+    let word_count = rendered.split_whitespace().count();
+    let words_per_minute = words_per_minute.max(1);
+    let reading_time_minutes = if word_count == 0 {
+        0
+    } else {
+        (word_count + words_per_minute - 1) / words_per_minute
+    };
+
+    PageAnalytics {
+        word_count,
+        reading_time_minutes,
+    }
+}
+
+/// Replace every `[[...]]` / `![[...]]` span in `content` with its display
+/// text (or target, if it has none), so word counting sees prose rather
+/// than link syntax. Unlike [`extract_links_and_embeds`], this doesn't mask
+/// fenced/inline code or track state across lines — it only needs to find
+/// and substitute bracket spans, not distinguish links from embeds.
+fn strip_wiki_link_markup(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let bytes = content.as_bytes();
+    let len = content.len();
+    let mut i = 0;
+
+    while i < len {
+        if i + 1 < len && bytes[i] == b'[' && bytes[i + 1] == b'[' {
+            let start = i + 2;
+            let mut end = start;
+            while end + 1 < len && !(bytes[end] == b']' && bytes[end + 1] == b']') {
+                end += 1;
+            }
+
+            if end + 1 < len && bytes[end] == b']' && bytes[end + 1] == b']' {
+                let link_content = &content[start..end];
+                let (target_part, display) = match link_content.find('|') {
+                    Some(pipe_pos) => (
+                        &link_content[..pipe_pos],
+                        Some(link_content[pipe_pos + 1..].trim()),
+                    ),
+                    None => (link_content, None),
+                };
+                let target = match target_part.find('#') {
+                    Some(hash_pos) => target_part[..hash_pos].trim(),
+                    None => target_part.trim(),
+                };
+
+                result.push_str(display.unwrap_or(target));
+                result.push(' ');
+                i = end + 2;
+                continue;
+            }
+        }
+
+        let ch = content[i..].chars().next().unwrap();
+        result.push(ch);
+        i += ch.len_utf8();
+    }
+
+    result
+}
+
+/// A single ATX heading found in a page's body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Heading {
+    /// Heading level: 1 for `#`, up to 6 for `######`.
+    pub level: u8,
+
+    /// The heading text, with the leading `#`s and any trailing closing
+    /// `#`s stripped.
+    pub text: String,
+
+    /// GitHub-style slug anchor: lowercased, non-alphanumeric characters
+    /// stripped, whitespace runs collapsed to `-`, disambiguated against
+    /// earlier headings with the same text via a `-1`, `-2`, ... suffix.
+    pub anchor: String,
+}
+
+/// A shortcode directive found in a page's body: either the inline form
+/// `{{ name(arg="value", n=3) }}` or the block form `{% name %} ... {%
+/// end %}`. Not rendered by the parser itself — this is the hook point
+/// for later rendering of custom widgets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shortcode {
+    /// The directive name.
+    pub name: String,
+
+    /// Arguments passed in the inline `name(...)` form, as raw strings
+    /// (quotes stripped). Empty for the block form, which takes no
+    /// arguments.
+    pub args: HashMap<String, String>,
+
+    /// The captured content between `{% name %}` and `{% end %}` for the
+    /// block form. `None` for the inline form.
+    pub body: Option<String>,
+
+    /// How many times this shortcode's name has appeared so far in the
+    /// document, counting this one (1 for the first occurrence, 2 for
+    /// the second, ...), so repeated invocations can be numbered.
+    pub nth: usize,
+}
+
+/// Frontmatter serialization format, detected from the delimiter at the
+/// very start of the file. See [`frontmatter_block`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FrontmatterFormat {
+    /// Between `---` lines.
+    Yaml,
+    /// Between `+++` lines.
+    Toml,
+    /// A leading `{`, closed by its brace-balanced `}`.
+    Json,
+    /// Between `<!--` and `-->`, as `key: value` lines.
+    Comment,
 }
 
-/// Parse YAML frontmatter from markdown content.
+/// Locate the frontmatter block (if any) at the very start of `content`.
 ///
-/// Frontmatter is expected to be at the very beginning of the file,
-/// enclosed between two `---` lines.
+/// Returns the detected format, the raw text between the delimiters
+/// (exclusive), and the remaining body text just past the closing
+/// delimiter — not yet stripped of its leading newline, since
+/// [`parse_frontmatter`] doesn't need that and [`strip_frontmatter`] does
+/// it itself.
+fn frontmatter_block(content: &str) -> Option<(FrontmatterFormat, &str, &str)> {
+    let trimmed = content.trim_start();
+
+    if let Some(after_first) = trimmed.strip_prefix("---") {
+        let pos = after_first.find("\n---")?;
+        let raw = after_first[..pos].trim();
+        let body = &after_first[pos + 4..];
+        return Some((FrontmatterFormat::Yaml, raw, body));
+    }
+
+    if let Some(after_first) = trimmed.strip_prefix("+++") {
+        let pos = after_first.find("\n+++")?;
+        let raw = after_first[..pos].trim();
+        let body = &after_first[pos + 4..];
+        return Some((FrontmatterFormat::Toml, raw, body));
+    }
+
+    if trimmed.starts_with('{') {
+        let end = matching_brace_end(trimmed)?;
+        let raw = &trimmed[..=end];
+        // `{{ name(args) }}` / `{% name %}` shortcode invocations also start
+        // with `{` and are brace-balanced, so confirm `raw` actually parses
+        // as a JSON object before claiming it as frontmatter — otherwise a
+        // document that opens with a shortcode gets that line silently
+        // swallowed here instead of reaching `extract_shortcodes`.
+        if matches!(
+            serde_json::from_str::<serde_json::Value>(raw),
+            Ok(serde_json::Value::Object(_))
+        ) {
+            let body = &trimmed[end + 1..];
+            return Some((FrontmatterFormat::Json, raw, body));
+        }
+    }
+
+    if let Some(after_first) = trimmed.strip_prefix("<!--") {
+        let pos = after_first.find("-->")?;
+        let raw = after_first[..pos].trim();
+        let body = &after_first[pos + 3..];
+        return Some((FrontmatterFormat::Comment, raw, body));
+    }
+
+    None
+}
+
+/// Find the byte index of the `}` that balances the leading `{` of `s`
+/// (which must start with `{`), respecting JSON string literals so a `}`
+/// inside a string doesn't end the match early.
+fn matching_brace_end(s: &str) -> Option<usize> {
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, b) in s.bytes().enumerate() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match b {
+            b'"' => in_string = true,
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Parse frontmatter from markdown content.
+///
+/// Frontmatter is expected to be at the very beginning of the file. The
+/// delimiter picks the format: `---` for YAML (the default), `+++` for
+/// TOML, a leading `{` for JSON, or a leading `<!-- key: value ... -->`
+/// HTML comment, whose lines are parsed directly as `key: value` pairs
+/// without a further serialization format.
 ///
 /// # Example
 /// ```text
@@ -41,22 +322,15 @@ pub struct ParsedPage {
 pub fn parse_frontmatter(content: &str) -> HashMap<String, Vec<String>> {
     let mut metadata = HashMap::new();
 
-    // Check if content starts with frontmatter delimiter
-    let trimmed = content.trim_start();
-    if !trimmed.starts_with("---") {
+    let Some((format, raw, _)) = frontmatter_block(content) else {
         return metadata;
-    }
+    };
 
-    // Find the closing delimiter
-    let after_first = &trimmed[3..];
-    let end_pos = after_first.find("\n---");
-
-    if let Some(pos) = end_pos {
-        let yaml_content = &after_first[..pos].trim();
-
-        // Parse the YAML
-        if let Ok(yaml_value) = serde_yaml::from_str::<serde_yaml::Value>(yaml_content) {
-            if let serde_yaml::Value::Mapping(map) = yaml_value {
+    match format {
+        FrontmatterFormat::Yaml => {
+            if let Ok(serde_yaml::Value::Mapping(map)) =
+                serde_yaml::from_str::<serde_yaml::Value>(raw)
+            {
                 for (key, value) in map {
                     if let serde_yaml::Value::String(key_str) = key {
                         let values = yaml_value_to_strings(&value);
@@ -67,6 +341,38 @@ pub fn parse_frontmatter(content: &str) -> HashMap<String, Vec<String>> {
                 }
             }
         }
+        FrontmatterFormat::Toml => {
+            if let Ok(toml::Value::Table(table)) = toml::from_str::<toml::Value>(raw) {
+                for (key, value) in table {
+                    let values = toml_value_to_strings(&value);
+                    if !values.is_empty() {
+                        metadata.insert(key, values);
+                    }
+                }
+            }
+        }
+        FrontmatterFormat::Json => {
+            if let Ok(serde_json::Value::Object(map)) = serde_json::from_str(raw) {
+                for (key, value) in map {
+                    let values = json_value_to_strings(&value);
+                    if !values.is_empty() {
+                        metadata.insert(key, values);
+                    }
+                }
+            }
+        }
+        FrontmatterFormat::Comment => {
+            for line in raw.lines() {
+                let Some((key, value)) = line.split_once(':') else {
+                    continue;
+                };
+                let key = key.trim().to_string();
+                let value = value.trim().to_string();
+                if !key.is_empty() && !value.is_empty() {
+                    metadata.insert(key, vec![value]);
+                }
+            }
+        }
     }
 
     metadata
@@ -92,22 +398,40 @@ fn yaml_value_to_strings(value: &serde_yaml::Value) -> Vec<String> {
     }
 }
 
+/// Convert a TOML value to a Vec<String>, mirroring
+/// [`yaml_value_to_strings`].
+fn toml_value_to_strings(value: &toml::Value) -> Vec<String> {
+    match value {
+        toml::Value::String(s) => vec![s.clone()],
+        toml::Value::Boolean(b) => vec![b.to_string()],
+        toml::Value::Integer(n) => vec![n.to_string()],
+        toml::Value::Float(n) => vec![n.to_string()],
+        toml::Value::Datetime(dt) => vec![dt.to_string()],
+        toml::Value::Array(arr) => arr.iter().flat_map(toml_value_to_strings).collect(),
+        toml::Value::Table(_) => vec![], // Skip nested tables for now
+    }
+}
+
+/// Convert a JSON value to a Vec<String>, mirroring
+/// [`yaml_value_to_strings`].
+fn json_value_to_strings(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::String(s) => vec![s.clone()],
+        serde_json::Value::Bool(b) => vec![b.to_string()],
+        serde_json::Value::Number(n) => vec![n.to_string()],
+        serde_json::Value::Array(arr) => arr.iter().flat_map(json_value_to_strings).collect(),
+        serde_json::Value::Null => vec![],
+        serde_json::Value::Object(_) => vec![], // Skip nested objects for now
+    }
+}
+
 /// Extract the content body without frontmatter.
 ///
 /// Returns the original content if no frontmatter is present.
 pub fn strip_frontmatter(content: &str) -> &str {
-    let trimmed = content.trim_start();
-    if !trimmed.starts_with("---") {
-        return content;
-    }
-
-    let after_first = &trimmed[3..];
-    if let Some(pos) = after_first.find("\n---") {
-        // Skip past the closing delimiter and any following newline
-        let after_closing = &after_first[pos + 4..];
-        after_closing.trim_start_matches('\n')
-    } else {
-        content
+    match frontmatter_block(content) {
+        Some((_, _, body)) => body.trim_start_matches('\n'),
+        None => content,
     }
 }
 
@@ -117,49 +441,170 @@ pub fn strip_frontmatter(content: &str) -> &str {
 /// - `[[PageName]]` - links to PageName, displayed as "PageName"
 /// - `[[PageName|Display Text]]` - links to PageName, displayed as "Display Text"
 ///
+/// `[[...]]` appearing inside a fenced code block (` ``` ` or `~~~`) or an
+/// inline code span (`` `...` ``) is not treated as a link, since that's a
+/// code sample rather than a real reference. A `![[...]]` transclusion is
+/// not returned here either; see [`extract_links_and_embeds`].
+///
 /// # Returns
 /// A vector of ParsedLink structs containing the target page name
 /// and optional display text.
 pub fn extract_wiki_links(content: &str) -> Vec<ParsedLink> {
+    extract_links_and_embeds(content).0
+}
+
+/// Extract both navigational wiki links and Obsidian-style transclusions
+/// (`![[Page]]`, `![[Page#Section]]`) from markdown content in a single
+/// pass, returning `(links, embeds)`.
+///
+/// A transclusion is written exactly like a wiki link but prefixed with
+/// `!`, and means "inline this page's content here" rather than "link to
+/// this page" — so it's kept in a separate list. Everything else (pipe
+/// display text, `#section` anchors, whitespace trimming, fenced/inline
+/// code masking) behaves the same for both.
+fn extract_links_and_embeds(content: &str) -> (Vec<ParsedLink>, Vec<ParsedLink>) {
     let mut links = Vec::new();
+    let mut embeds = Vec::new();
+    let mut seen_links = std::collections::HashSet::new();
+    let mut seen_embeds = std::collections::HashSet::new();
+    let mut fence: Option<(char, usize)> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if let Some((marker, marker_len)) = fence_marker(trimmed) {
+            fence = match fence {
+                // CommonMark: a fence only closes on the same character,
+                // at least as long as the one that opened it.
+                Some((open_ch, open_len)) if open_ch == marker && marker_len >= open_len => None,
+                Some(open) => Some(open),
+                None => Some((marker, marker_len)),
+            };
+            continue;
+        }
+
+        if fence.is_some() {
+            continue;
+        }
+
+        extract_wiki_links_from_line(
+            &mask_inline_code(line),
+            &mut links,
+            &mut seen_links,
+            &mut embeds,
+            &mut seen_embeds,
+        );
+    }
+
+    (links, embeds)
+}
+
+/// The fence character (`` ` `` or `~`) and run length a trimmed line
+/// opens or closes a fenced code block with, if it's a fence line (at
+/// least three of the same character in a row at the start). The length
+/// matters because CommonMark only lets a fence close with a run at
+/// least as long as the one that opened it — a 3-backtick block isn't
+/// closed by a 2-backtick line.
+fn fence_marker(trimmed: &str) -> Option<(char, usize)> {
+    let marker = trimmed.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let len = trimmed.chars().take_while(|&c| c == marker).count();
+    (len >= 3).then_some((marker, len))
+}
 
-    // Pattern: [[PageName]] or [[PageName|Display Text]]
+/// Replace every inline code span (`` `...` ``) on `line` with spaces, so
+/// wiki-link-shaped text inside one is never matched. Assumes balanced
+/// backticks; an unbalanced backtick masks the rest of the line, so a
+/// wiki link after it on the same line is not extracted.
+fn mask_inline_code(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut in_code = false;
+    for ch in line.chars() {
+        if ch == '`' {
+            in_code = !in_code;
+            result.push(' ');
+        } else if in_code {
+            result.push(' ');
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+/// Find every `[[PageName]]` / `[[PageName|Display Text]]` in `line` and
+/// push the ones not already in `seen_links` onto `links`, preserving
+/// order. A match prefixed with `!` (`![[PageName]]`) is an Obsidian-style
+/// transclusion instead, and is routed to `embeds`/`seen_embeds`.
+///
+/// A target may carry a `/`-separated namespace path (`folder/Sub/Page`,
+/// kept as-is) and/or a `#Section` anchor, parsed off into
+/// [`ParsedLink::anchor`]. A bare `[[#Section]]` yields an empty target
+/// with just an anchor, for intra-page links.
+fn extract_wiki_links_from_line(
+    line: &str,
+    links: &mut Vec<ParsedLink>,
+    seen_links: &mut std::collections::HashSet<String>,
+    embeds: &mut Vec<ParsedLink>,
+    seen_embeds: &mut std::collections::HashSet<String>,
+) {
+    // Pattern: [[PageName]] or [[PageName|Display Text]], or ![[PageName]]
     // We parse this manually for simplicity and efficiency.
 
     let mut i = 0;
-    let content_bytes = content.as_bytes();
-    let len = content.len();
+    let line_bytes = line.as_bytes();
+    let len = line.len();
 
     while i < len {
         // Look for [[
-        if i + 1 < len && content_bytes[i] == b'[' && content_bytes[i + 1] == b'[' {
+        if i + 1 < len && line_bytes[i] == b'[' && line_bytes[i + 1] == b'[' {
+            // `\[[NotALink]]` is an escaped literal, not a wiki link.
+            if i > 0 && line_bytes[i - 1] == b'\\' {
+                i += 1;
+                continue;
+            }
+            let is_embed = i > 0 && line_bytes[i - 1] == b'!';
+
             // Found opening [[, now find the closing ]]
             let start = i + 2;
             let mut end = start;
 
             // Find the closing ]]
             while end + 1 < len {
-                if content_bytes[end] == b']' && content_bytes[end + 1] == b']' {
+                if line_bytes[end] == b']' && line_bytes[end + 1] == b']' {
                     break;
                 }
                 end += 1;
             }
 
-            if end + 1 < len && content_bytes[end] == b']' && content_bytes[end + 1] == b']' {
+            if end + 1 < len && line_bytes[end] == b']' && line_bytes[end + 1] == b']' {
                 // Extract the content between [[ and ]]
-                let link_content = &content[start..end];
+                let link_content = &line[start..end];
 
                 // Check if there's a pipe for display text
-                if let Some(pipe_pos) = link_content.find('|') {
-                    let target = link_content[..pipe_pos].trim().to_string();
-                    let display = link_content[pipe_pos + 1..].trim().to_string();
-                    if !target.is_empty() {
-                        links.push(ParsedLink::new(target, Some(display)));
-                    }
-                } else {
-                    let target = link_content.trim().to_string();
-                    if !target.is_empty() {
-                        links.push(ParsedLink::new(target, None));
+                let (target_part, display) = match link_content.find('|') {
+                    Some(pipe_pos) => (
+                        &link_content[..pipe_pos],
+                        Some(link_content[pipe_pos + 1..].trim().to_string()),
+                    ),
+                    None => (link_content, None),
+                };
+
+                let (target, anchor) = match target_part.find('#') {
+                    Some(hash_pos) => (
+                        target_part[..hash_pos].trim().to_string(),
+                        Some(target_part[hash_pos + 1..].trim().to_string()),
+                    ),
+                    None => (target_part.trim().to_string(), None),
+                };
+
+                if !target.is_empty() || anchor.is_some() {
+                    let dedup_key = format!("{}#{}", target, anchor.as_deref().unwrap_or(""));
+                    let (list, seen) = if is_embed {
+                        (&mut *embeds, &mut *seen_embeds)
+                    } else {
+                        (&mut *links, &mut *seen_links)
+                    };
+                    if seen.insert(dedup_key) {
+                        list.push(ParsedLink::with_anchor(target, display, anchor));
                     }
                 }
 
@@ -170,32 +615,290 @@ pub fn extract_wiki_links(content: &str) -> Vec<ParsedLink> {
 
         i += 1;
     }
+}
+
+/// Scan `body` for ATX headings (`#` through `######`), assigning each a
+/// GitHub-style slug anchor unique within the document. Lines inside a
+/// fenced code block are skipped, the same as in [`extract_links_and_embeds`],
+/// so a `#`-prefixed comment in a shell/Python/YAML snippet isn't misread
+/// as a heading.
+fn extract_headings(body: &str) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut seen_slugs: HashMap<String, usize> = HashMap::new();
+    let mut fence: Option<(char, usize)> = None;
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+
+        if let Some((marker, marker_len)) = fence_marker(trimmed) {
+            fence = match fence {
+                Some((open_ch, open_len)) if open_ch == marker && marker_len >= open_len => None,
+                Some(open) => Some(open),
+                None => Some((marker, marker_len)),
+            };
+            continue;
+        }
+
+        if fence.is_some() {
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+        if hashes == 0 || hashes > 6 {
+            continue;
+        }
+
+        let rest = &trimmed[hashes..];
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            // e.g. "#hashtag" - not an ATX heading without a space after the #s
+            continue;
+        }
+
+        let text = rest.trim().trim_end_matches('#').trim().to_string();
+        let anchor = unique_slug(&text, &mut seen_slugs);
+        headings.push(Heading {
+            level: hashes as u8,
+            text,
+            anchor,
+        });
+    }
+
+    headings
+}
+
+/// Slugify `text` GitHub-style, disambiguating against earlier headings
+/// with the same base slug via a `-1`, `-2`, ... suffix.
+fn unique_slug(text: &str, seen: &mut HashMap<String, usize>) -> String {
+    let base = slugify_text(text);
+    let count = seen.entry(base.clone()).or_insert(0);
+    let slug = if *count == 0 {
+        base
+    } else {
+        format!("{}-{}", base, count)
+    };
+    *count += 1;
+    slug
+}
+
+/// Lowercase `text`, strip non-alphanumeric characters, and collapse
+/// whitespace (and existing `-`) runs into a single `-`.
+fn slugify_text(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut last_was_dash = true; // avoids a leading '-'
 
-    // Remove duplicates while preserving order
-    let mut seen = std::collections::HashSet::new();
-    links.retain(|link| seen.insert(link.target.clone()));
+    for ch in text.chars() {
+        let lower = ch.to_ascii_lowercase();
+        if lower.is_alphanumeric() {
+            slug.push(lower);
+            last_was_dash = false;
+        } else if (lower.is_whitespace() || lower == '-') && !last_was_dash {
+            slug.push('-');
+            last_was_dash = true;
+        }
+    }
 
-    links
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Scan `body` for shortcode directives: the inline form `{{
+/// name(arg="value") }}` and the paired block form `{% name %} ... {%
+/// end %}`. Block forms are not nested; the first `{% end %}` after an
+/// opening tag closes it.
+fn extract_shortcodes(body: &str) -> Vec<Shortcode> {
+    let mut shortcodes = Vec::new();
+    let mut seen_counts: HashMap<String, usize> = HashMap::new();
+    let mut cursor = 0;
+
+    while cursor < body.len() {
+        let rest = &body[cursor..];
+        let inline_pos = rest.find("{{");
+        let block_pos = rest.find("{%");
+
+        let next = match (inline_pos, block_pos) {
+            (None, None) => break,
+            (Some(i), None) => (i, true),
+            (None, Some(b)) => (b, false),
+            (Some(i), Some(b)) => (i.min(b), i <= b),
+        };
+        let (offset, is_inline) = next;
+        let start = cursor + offset;
+
+        if is_inline {
+            match rest[offset + 2..].find("}}") {
+                Some(end_offset) => {
+                    let inner_start = start + 2;
+                    let inner_end = inner_start + end_offset;
+                    let (name, args) = parse_invocation(&body[inner_start..inner_end]);
+                    if !name.is_empty() {
+                        let nth = next_nth(&mut seen_counts, &name);
+                        shortcodes.push(Shortcode { name, args, body: None, nth });
+                    }
+                    cursor = inner_end + 2;
+                }
+                None => break,
+            }
+        } else {
+            match rest[offset + 2..].find("%}") {
+                Some(tag_end_offset) => {
+                    let tag_start = start + 2;
+                    let tag_end = tag_start + tag_end_offset;
+                    let name = body[tag_start..tag_end].trim().to_string();
+                    let after_open = tag_end + 2;
+
+                    match body[after_open..].find("{% end %}") {
+                        Some(end_offset) if !name.is_empty() => {
+                            let block_body = body[after_open..after_open + end_offset].to_string();
+                            let nth = next_nth(&mut seen_counts, &name);
+                            shortcodes.push(Shortcode {
+                                name,
+                                args: HashMap::new(),
+                                body: Some(block_body),
+                                nth,
+                            });
+                            cursor = after_open + end_offset + "{% end %}".len();
+                        }
+                        _ => {
+                            cursor = after_open;
+                        }
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+
+    shortcodes
+}
+
+/// Bump and return the running occurrence count for `name` in
+/// [`extract_shortcodes`]'s `seen_counts` map.
+fn next_nth(seen_counts: &mut HashMap<String, usize>, name: &str) -> usize {
+    let count = seen_counts.entry(name.to_string()).or_insert(0);
+    *count += 1;
+    *count
+}
+
+/// Parse a `name` or `name(arg="value", n=3)` invocation into its name
+/// and argument map.
+fn parse_invocation(s: &str) -> (String, HashMap<String, String>) {
+    let s = s.trim();
+    match (s.find('('), s.rfind(')')) {
+        (Some(open), Some(close)) if open < close => {
+            let name = s[..open].trim().to_string();
+            let args = parse_args(&s[open + 1..close]);
+            (name, args)
+        }
+        _ => (s.to_string(), HashMap::new()),
+    }
+}
+
+/// Parse a comma-separated `key="value", key2=value2` argument list,
+/// splitting on commas outside of quoted strings.
+fn parse_args(s: &str) -> HashMap<String, String> {
+    let mut args = HashMap::new();
+
+    for part in split_args(s) {
+        let Some(eq_pos) = part.find('=') else {
+            continue;
+        };
+        let key = part[..eq_pos].trim().to_string();
+        let mut value = part[eq_pos + 1..].trim().to_string();
+        if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+            value = value[1..value.len() - 1].to_string();
+        }
+        if !key.is_empty() {
+            args.insert(key, value);
+        }
+    }
+
+    args
+}
+
+/// Split `s` on commas, ignoring commas inside double-quoted strings.
+fn split_args(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_string = false;
+    let mut start = 0;
+
+    for (i, ch) in s.char_indices() {
+        if ch == '"' {
+            in_string = !in_string;
+        } else if ch == ',' && !in_string {
+            parts.push(&s[start..i]);
+            start = i + 1;
+        }
+    }
+    if start < s.len() {
+        parts.push(&s[start..]);
+    }
+
+    parts
+}
+
+/// Compute a stable hex-encoded content hash for a page body, used to
+/// synthesize a page id when no `page-id` metadata key is present.
+fn hash_body(body: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(body.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Parse a single-valued metadata key as an RFC 3339 timestamp.
+fn parse_timestamp(
+    metadata: &HashMap<String, Vec<String>>,
+    key: &str,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let raw = metadata.get(key)?.first()?;
+    chrono::DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|dt| dt.with_timezone(&chrono::Utc))
 }
 
 /// Parse a complete markdown file.
 ///
-/// Extracts frontmatter metadata and wiki links from the content.
+/// Extracts frontmatter metadata, wiki links, transclusions, shortcode
+/// directives, the title, heading anchors, and a content-derived page id
+/// (with optional `created`/`modified` timestamps) from the content. See
+/// [`ParsedPage::id`] for the id's current (parse-time-only) scope.
 ///
 /// # Arguments
 /// * `content` - The full markdown file content
 ///
 /// # Returns
-/// A ParsedPage containing metadata, links, and the content body.
+/// A ParsedPage containing metadata, links, embeds, shortcodes, title,
+/// headings, id, timestamps, and the content body.
 pub fn parse_markdown(content: &str) -> ParsedPage {
     let metadata = parse_frontmatter(content);
     let body = strip_frontmatter(content);
-    let links = extract_wiki_links(body);
+    let (links, embeds) = extract_links_and_embeds(body);
+    let headings = extract_headings(body);
+
+    let title = headings
+        .iter()
+        .find(|heading| heading.level == 1)
+        .map(|heading| heading.text.clone())
+        .or_else(|| metadata.get("title").and_then(|values| values.first().cloned()));
+    let shortcodes = extract_shortcodes(body);
+    let id = metadata
+        .get("page-id")
+        .and_then(|values| values.first().cloned())
+        .unwrap_or_else(|| hash_body(body));
+    let created = parse_timestamp(&metadata, "created");
+    let modified = parse_timestamp(&metadata, "modified");
+    let analytics = page_analytics(body);
 
     ParsedPage {
         metadata,
         links,
         content: body.to_string(),
+        title,
+        headings,
+        embeds,
+        shortcodes,
+        id,
+        created,
+        modified,
+        analytics,
     }
 }
 
@@ -263,6 +966,71 @@ ratio: 3.14
         assert_eq!(metadata.get("ratio"), Some(&vec!["3.14".to_string()]));
     }
 
+    #[test]
+    fn test_parse_frontmatter_toml() {
+        let content = r#"+++
+status = "draft"
+tags = ["rust", "wiki"]
++++
+# Content"#;
+
+        let metadata = parse_frontmatter(content);
+        assert_eq!(metadata.get("status"), Some(&vec!["draft".to_string()]));
+        let tags = metadata.get("tags").unwrap();
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains(&"rust".to_string()));
+    }
+
+    #[test]
+    fn test_strip_frontmatter_toml() {
+        let content = r#"+++
+status = "draft"
++++
+# Title"#;
+
+        let body = strip_frontmatter(content);
+        assert!(body.starts_with("# Title"));
+        assert!(!body.contains("status"));
+    }
+
+    #[test]
+    fn test_parse_frontmatter_json() {
+        let content = r#"{
+  "status": "draft",
+  "tags": ["rust", "wiki"]
+}
+# Content"#;
+
+        let metadata = parse_frontmatter(content);
+        assert_eq!(metadata.get("status"), Some(&vec!["draft".to_string()]));
+        let tags = metadata.get("tags").unwrap();
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains(&"wiki".to_string()));
+    }
+
+    #[test]
+    fn test_strip_frontmatter_json() {
+        let content = "{\"status\": \"draft\"}\n# Title\n\nContent here.";
+        let body = strip_frontmatter(content);
+        assert!(body.starts_with("# Title"));
+        assert!(!body.contains("status"));
+    }
+
+    #[test]
+    fn test_strip_frontmatter_does_not_eat_leading_shortcode() {
+        // `{{ toc }}` opens with `{` and is brace-balanced, same as JSON
+        // frontmatter, but isn't a JSON object — it must not be mistaken
+        // for frontmatter and swallowed out of the body.
+        let content = "{{ toc }}\n\nSome content.";
+        let body = strip_frontmatter(content);
+        assert_eq!(body, content);
+        assert!(parse_frontmatter(content).is_empty());
+
+        let shortcodes = extract_shortcodes(body);
+        assert_eq!(shortcodes.len(), 1);
+        assert_eq!(shortcodes[0].name, "toc");
+    }
+
     #[test]
     fn test_strip_frontmatter() {
         let content = r#"---
@@ -353,6 +1121,315 @@ Also check [[About|About Us]] and [[Contact]].
         assert_eq!(links[0].display_text, Some("Display Text".to_string()));
     }
 
+    #[test]
+    fn test_extract_wiki_links_ignores_fenced_code_block() {
+        let content = "See [[Real]].\n\n```\n[[NotALink]]\n```\n\nAnd [[AlsoReal]].";
+        let links = extract_wiki_links(content);
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].target, "Real");
+        assert_eq!(links[1].target, "AlsoReal");
+    }
+
+    #[test]
+    fn test_extract_wiki_links_ignores_tilde_fenced_code_block() {
+        let content = "~~~\n[[NotALink]]\n~~~\n[[Real]]";
+        let links = extract_wiki_links(content);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "Real");
+    }
+
+    #[test]
+    fn test_extract_wiki_links_fence_not_closed_by_shorter_run() {
+        // The block opens with 3 backticks; a 2-backtick line isn't a valid
+        // closing fence per CommonMark, so everything through the real
+        // closing fence (also 3 backticks) stays masked.
+        let content = "```\n[[NotALink]]\n``\nstill inside\n```\n[[Real]]";
+        let links = extract_wiki_links(content);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "Real");
+    }
+
+    #[test]
+    fn test_extract_wiki_links_ignores_inline_code_span() {
+        let content = "Use `[[NotALink]]` syntax, but [[Real]] links normally.";
+        let links = extract_wiki_links(content);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "Real");
+    }
+
+    #[test]
+    fn test_extract_wiki_links_with_section_anchor() {
+        let content = "See [[Page#Section]] for details.";
+        let links = extract_wiki_links(content);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "Page");
+        assert_eq!(links[0].anchor, Some("Section".to_string()));
+    }
+
+    #[test]
+    fn test_extract_wiki_links_skips_backslash_escaped_link() {
+        let content = "Literal \\[[NotALink]] here, but [[Real]] still works.";
+        let links = extract_wiki_links(content);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "Real");
+    }
+
+    #[test]
+    fn test_extract_wiki_links_with_namespace_path() {
+        let content = "See [[folder/Sub/Page]] for details.";
+        let links = extract_wiki_links(content);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "folder/Sub/Page");
+        assert_eq!(links[0].anchor, None);
+    }
+
+    #[test]
+    fn test_extract_wiki_links_with_namespace_anchor_and_display() {
+        let content = "See [[folder/Page#Section|Display]] for details.";
+        let links = extract_wiki_links(content);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "folder/Page");
+        assert_eq!(links[0].anchor, Some("Section".to_string()));
+        assert_eq!(links[0].display_text, Some("Display".to_string()));
+    }
+
+    #[test]
+    fn test_extract_wiki_links_bare_anchor_has_empty_target() {
+        let content = "See [[#Section]] for details.";
+        let links = extract_wiki_links(content);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, "");
+        assert_eq!(links[0].anchor, Some("Section".to_string()));
+    }
+
+    #[test]
+    fn test_parse_markdown_embed_is_separate_from_links() {
+        let content = "See [[Real]] and embed ![[Snippet]].";
+        let parsed = parse_markdown(content);
+
+        assert_eq!(parsed.links.len(), 1);
+        assert_eq!(parsed.links[0].target, "Real");
+        assert_eq!(parsed.embeds.len(), 1);
+        assert_eq!(parsed.embeds[0].target, "Snippet");
+    }
+
+    #[test]
+    fn test_parse_markdown_embed_with_section_anchor() {
+        let content = "![[Page#Section]]";
+        let parsed = parse_markdown(content);
+
+        assert_eq!(parsed.embeds.len(), 1);
+        assert_eq!(parsed.embeds[0].target, "Page");
+        assert_eq!(parsed.embeds[0].anchor, Some("Section".to_string()));
+        assert!(parsed.links.is_empty());
+    }
+
+    #[test]
+    fn test_page_analytics_counts_words_and_rounds_up_reading_time() {
+        let words: Vec<&str> = std::iter::repeat("word").take(250).collect();
+        let content = words.join(" ");
+
+        let analytics = page_analytics(&content);
+        assert_eq!(analytics.word_count, 250);
+        // 250 words / 200 wpm = 1.25, rounded up to 2 minutes.
+        assert_eq!(analytics.reading_time_minutes, 2);
+    }
+
+    #[test]
+    fn test_page_analytics_counts_wiki_link_display_text_not_markup() {
+        let content = "See [[SomePage|the linked page]] for more.";
+        let analytics = page_analytics(content);
+        // "See", "the", "linked", "page", "for", "more." - not the raw
+        // [[SomePage|...]] syntax.
+        assert_eq!(analytics.word_count, 6);
+    }
+
+    #[test]
+    fn test_page_analytics_falls_back_to_target_when_no_display_text() {
+        let content = "[[SomePage]]";
+        let analytics = page_analytics(content);
+        assert_eq!(analytics.word_count, 1);
+    }
+
+    #[test]
+    fn test_page_analytics_empty_content_has_zero_reading_time() {
+        let analytics = page_analytics("");
+        assert_eq!(analytics.word_count, 0);
+        assert_eq!(analytics.reading_time_minutes, 0);
+    }
+
+    #[test]
+    fn test_parse_markdown_populates_analytics_from_body() {
+        let content = "---\ntitle: Test\n---\n# Heading\n\nSome words here.";
+        let parsed = parse_markdown(content);
+        // "# Heading Some words here." - includes the ATX `#` marker,
+        // since analytics only strips wiki-link markup, not Markdown syntax.
+        assert_eq!(parsed.analytics.word_count, 5);
+    }
+
+    #[test]
+    fn test_parse_frontmatter_html_comment() {
+        let content = "<!--\npage-id: abc123\nstatus: draft\n-->\n# Content";
+        let metadata = parse_frontmatter(content);
+        assert_eq!(metadata.get("page-id"), Some(&vec!["abc123".to_string()]));
+        assert_eq!(metadata.get("status"), Some(&vec!["draft".to_string()]));
+    }
+
+    #[test]
+    fn test_strip_frontmatter_html_comment() {
+        let content = "<!--\npage-id: abc123\n-->\n# Title\n\nBody.";
+        let body = strip_frontmatter(content);
+        assert!(body.starts_with("# Title"));
+        assert!(!body.contains("page-id"));
+    }
+
+    #[test]
+    fn test_parse_markdown_uses_page_id_metadata_when_present() {
+        let content = "<!--\npage-id: stable-123\n-->\n# Title";
+        let parsed = parse_markdown(content);
+        assert_eq!(parsed.id, "stable-123");
+    }
+
+    #[test]
+    fn test_parse_markdown_synthesizes_id_from_content_hash_when_absent() {
+        let content = "# Title\n\nSame body.";
+        let parsed_a = parse_markdown(content);
+        let parsed_b = parse_markdown(content);
+        assert_eq!(parsed_a.id, parsed_b.id);
+        assert_ne!(parsed_a.id, "");
+
+        let different = parse_markdown("# Title\n\nDifferent body.");
+        assert_ne!(parsed_a.id, different.id);
+    }
+
+    #[test]
+    fn test_parse_markdown_parses_created_and_modified_timestamps() {
+        let content = "<!--\ncreated: 2025-01-02T03:04:05Z\nmodified: 2025-06-07T08:09:10Z\n-->\n# Title";
+        let parsed = parse_markdown(content);
+        assert_eq!(
+            parsed.created.unwrap().to_rfc3339(),
+            "2025-01-02T03:04:05+00:00"
+        );
+        assert_eq!(
+            parsed.modified.unwrap().to_rfc3339(),
+            "2025-06-07T08:09:10+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_markdown_timestamps_none_when_absent() {
+        let parsed = parse_markdown("# Title");
+        assert_eq!(parsed.created, None);
+        assert_eq!(parsed.modified, None);
+    }
+
+    #[test]
+    fn test_extract_shortcodes_inline_with_args() {
+        let body = r#"Some text {{ figure(src="a.png", n=3) }} more text."#;
+        let shortcodes = extract_shortcodes(body);
+
+        assert_eq!(shortcodes.len(), 1);
+        assert_eq!(shortcodes[0].name, "figure");
+        assert_eq!(shortcodes[0].args.get("src"), Some(&"a.png".to_string()));
+        assert_eq!(shortcodes[0].args.get("n"), Some(&"3".to_string()));
+        assert_eq!(shortcodes[0].body, None);
+        assert_eq!(shortcodes[0].nth, 1);
+    }
+
+    #[test]
+    fn test_extract_shortcodes_inline_without_args() {
+        let body = "{{ toc }}";
+        let shortcodes = extract_shortcodes(body);
+        assert_eq!(shortcodes[0].name, "toc");
+        assert!(shortcodes[0].args.is_empty());
+    }
+
+    #[test]
+    fn test_extract_shortcodes_block_form_captures_body() {
+        let body = "{% note %}\nThis is important.\n{% end %}";
+        let shortcodes = extract_shortcodes(body);
+
+        assert_eq!(shortcodes.len(), 1);
+        assert_eq!(shortcodes[0].name, "note");
+        assert_eq!(shortcodes[0].body, Some("\nThis is important.\n".to_string()));
+    }
+
+    #[test]
+    fn test_extract_shortcodes_numbers_repeated_invocations() {
+        let body = "{{ warn }} and again {{ warn }} and {% warn %}body{% end %}";
+        let shortcodes = extract_shortcodes(body);
+
+        assert_eq!(shortcodes.len(), 3);
+        assert_eq!(shortcodes[0].nth, 1);
+        assert_eq!(shortcodes[1].nth, 2);
+        assert_eq!(shortcodes[2].nth, 3);
+    }
+
+    #[test]
+    fn test_extract_headings_records_level_text_and_anchor() {
+        let body = "# Welcome\n\n## Getting Started\n\nSome text.\n\n### Sub Section!";
+        let headings = extract_headings(body);
+
+        assert_eq!(headings.len(), 3);
+        assert_eq!(headings[0], Heading { level: 1, text: "Welcome".to_string(), anchor: "welcome".to_string() });
+        assert_eq!(headings[1].anchor, "getting-started");
+        assert_eq!(headings[2].anchor, "sub-section");
+    }
+
+    #[test]
+    fn test_extract_headings_disambiguates_duplicate_slugs() {
+        let body = "# Notes\n\n## Notes\n\n## Notes";
+        let headings = extract_headings(body);
+
+        assert_eq!(headings[0].anchor, "notes");
+        assert_eq!(headings[1].anchor, "notes-1");
+        assert_eq!(headings[2].anchor, "notes-2");
+    }
+
+    #[test]
+    fn test_extract_headings_ignores_hashtag_without_space() {
+        let body = "This is a #hashtag, not a heading.";
+        let headings = extract_headings(body);
+        assert!(headings.is_empty());
+    }
+
+    #[test]
+    fn test_extract_headings_strips_closing_hashes() {
+        let body = "## Section ##";
+        let headings = extract_headings(body);
+        assert_eq!(headings[0].text, "Section");
+    }
+
+    #[test]
+    fn test_extract_headings_ignores_fenced_code_block() {
+        let body = "# Real Heading\n\n```python\n# Not a heading, just a comment\n```\n\n## Also Real";
+        let headings = extract_headings(body);
+        assert_eq!(headings.len(), 2);
+        assert_eq!(headings[0].text, "Real Heading");
+        assert_eq!(headings[1].text, "Also Real");
+    }
+
+    #[test]
+    fn test_parse_markdown_title_from_h1() {
+        let content = "# My Page\n\nSome content.";
+        let parsed = parse_markdown(content);
+        assert_eq!(parsed.title, Some("My Page".to_string()));
+    }
+
+    #[test]
+    fn test_parse_markdown_title_falls_back_to_frontmatter() {
+        let content = "---\ntitle: Frontmatter Title\n---\nNo heading here.";
+        let parsed = parse_markdown(content);
+        assert_eq!(parsed.title, Some("Frontmatter Title".to_string()));
+    }
+
+    #[test]
+    fn test_parse_markdown_title_none_when_absent() {
+        let content = "Just a paragraph, no heading or frontmatter title.";
+        let parsed = parse_markdown(content);
+        assert_eq!(parsed.title, None);
+    }
+
     #[test]
     fn test_parse_markdown_complete() {
         let content = r#"---
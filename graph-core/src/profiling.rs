@@ -0,0 +1,165 @@
+//! Lightweight self-profiling for [`crate::graph::WikiGraph`] operations.
+//!
+//! Disabled by default, so the timers cost nothing until a caller opts in
+//! via [`Profiler::set_enabled`]. Used by `GraphEngine::enable_profiling`
+//! and `GraphEngine::get_profile` to let Python operators diagnose slow
+//! rebuilds and expensive filter combinations without external tracing
+//! tooling.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Timing and count totals accumulated for one named operation.
+#[derive(Clone, Copy, Debug, Default)]
+struct OperationStats {
+    count: u64,
+    total: Duration,
+    max: Duration,
+    /// Extra item count for operations that process multiple items per
+    /// call, e.g. files parsed during a `rebuild`. Zero for operations
+    /// that never report one.
+    items: u64,
+}
+
+/// One row of [`Profiler::snapshot`]: a named operation plus its
+/// accumulated call count and timings, in milliseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileEntry {
+    pub operation: String,
+    pub count: u64,
+    pub total_ms: f64,
+    pub mean_ms: f64,
+    pub max_ms: f64,
+    /// Total items processed across all calls (e.g. files parsed), for
+    /// operations that report one. Zero otherwise.
+    pub items: u64,
+}
+
+/// Accumulates timing and call counts for named operations (directory
+/// scan, markdown parse, link resolution, query evaluation, ...), gated
+/// behind an enabled flag so that disabled profiling costs a single
+/// branch and no allocation.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    enabled: bool,
+    stats: HashMap<String, OperationStats>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Turn profiling on or off. Disabling clears all accumulated stats,
+    /// so a fresh `enable_profiling(true)` always starts from zero.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.stats.clear();
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Record one call to `operation` taking `elapsed`. A no-op while
+    /// disabled.
+    pub fn record(&mut self, operation: &str, elapsed: Duration) {
+        self.record_items(operation, elapsed, 0);
+    }
+
+    /// Record one call to `operation` taking `elapsed`, and add `items` to
+    /// its running item count (e.g. files parsed during a `rebuild`). A
+    /// no-op while disabled.
+    pub fn record_items(&mut self, operation: &str, elapsed: Duration, items: u64) {
+        if !self.enabled {
+            return;
+        }
+        let entry = self.stats.entry(operation.to_string()).or_default();
+        entry.count += 1;
+        entry.total += elapsed;
+        entry.max = entry.max.max(elapsed);
+        entry.items += items;
+    }
+
+    /// Current snapshot of every operation recorded since profiling was
+    /// last enabled, sorted by operation name.
+    pub fn snapshot(&self) -> Vec<ProfileEntry> {
+        let mut entries: Vec<ProfileEntry> = self
+            .stats
+            .iter()
+            .map(|(operation, stats)| ProfileEntry {
+                operation: operation.clone(),
+                count: stats.count,
+                total_ms: duration_ms(stats.total),
+                mean_ms: if stats.count == 0 {
+                    0.0
+                } else {
+                    duration_ms(stats.total) / stats.count as f64
+                },
+                max_ms: duration_ms(stats.max),
+                items: stats.items,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.operation.cmp(&b.operation));
+        entries
+    }
+}
+
+fn duration_ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    #[test]
+    fn test_disabled_profiler_records_nothing() {
+        let mut profiler = Profiler::new();
+        profiler.record("scan", Duration::from_millis(5));
+        assert!(profiler.snapshot().is_empty());
+    }
+
+    #[test]
+    fn test_enabled_profiler_accumulates_count_and_timings() {
+        let mut profiler = Profiler::new();
+        profiler.set_enabled(true);
+        profiler.record("scan", Duration::from_millis(10));
+        profiler.record("scan", Duration::from_millis(30));
+
+        let snapshot = profiler.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].operation, "scan");
+        assert_eq!(snapshot[0].count, 2);
+        assert!((snapshot[0].total_ms - 40.0).abs() < 1.0);
+        assert!((snapshot[0].mean_ms - 20.0).abs() < 1.0);
+        assert!((snapshot[0].max_ms - 30.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_record_items_accumulates_item_count() {
+        let mut profiler = Profiler::new();
+        profiler.set_enabled(true);
+        profiler.record_items("rebuild", Duration::from_millis(5), 3);
+        profiler.record_items("rebuild", Duration::from_millis(5), 2);
+
+        let snapshot = profiler.snapshot();
+        assert_eq!(snapshot[0].items, 5);
+    }
+
+    #[test]
+    fn test_disabling_profiler_clears_accumulated_stats() {
+        let mut profiler = Profiler::new();
+        profiler.set_enabled(true);
+        profiler.record("scan", Duration::from_millis(1));
+        profiler.set_enabled(false);
+        assert!(profiler.snapshot().is_empty());
+
+        profiler.set_enabled(true);
+        sleep(Duration::from_millis(1));
+        assert!(profiler.snapshot().is_empty());
+    }
+}
@@ -7,8 +7,9 @@
 use crate::graph::WikiGraph;
 use crate::models::PageNode;
 use pyo3::prelude::*;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Filter types for querying wiki pages.
 ///
@@ -25,16 +26,76 @@ pub enum Filter {
     Contains { key: String, substring: String },
 
     /// Match pages where any value in metadata[key] matches the regex pattern.
-    Matches { key: String, pattern: String },
+    ///
+    /// The regex is compiled once, at filter construction time (see
+    /// [`Filter::matches`]), rather than on every page scanned.
+    Matches { key: String, regex: Arc<Regex> },
 
-    /// Match pages that link to the specified target page.
+    /// Match pages that link to the specified target page. Navigational
+    /// links only — a page that only `![[Embeds]]` the target doesn't match.
     LinksTo { page: String },
 
     /// Match pages that are linked from the specified source page.
+    /// Navigational links only, same as [`Filter::LinksTo`].
     LinkedFrom { page: String },
+
+    /// Match pages where every child filter matches.
+    And(Vec<Filter>),
+
+    /// Match pages where at least one child filter matches.
+    Or(Vec<Filter>),
+
+    /// Match pages where the child filter does not match.
+    Not(Box<Filter>),
+
+    /// Match pages reachable from/to `page` within `max_depth` link hops.
+    ///
+    /// `direction` selects whether reachability is traced via outlinks
+    /// (descendants) or backlinks (ancestors), following navigational links
+    /// only. `max_depth` of `None` means unbounded.
+    Reachable {
+        page: String,
+        max_depth: Option<usize>,
+        direction: LinkDirection,
+    },
+}
+
+/// Direction to traverse when testing transitive link reachability.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkDirection {
+    /// Follow outlinks (descendants of `page`).
+    Outlink,
+    /// Follow backlinks (ancestors of `page`).
+    Backlink,
 }
 
 impl Filter {
+    /// Build a `Matches` filter, compiling the regex once up front.
+    ///
+    /// Returns an error immediately if `pattern` is not a valid regex,
+    /// instead of silently matching nothing at query time.
+    ///
+    /// # Arguments
+    /// * `key` - The metadata key to check
+    /// * `pattern` - The regex pattern to compile
+    /// * `case_insensitive` - Whether matching should ignore case
+    /// * `multi_line` - Whether `^`/`$` match at line boundaries rather than only start/end of input
+    pub fn matches(
+        key: String,
+        pattern: &str,
+        case_insensitive: bool,
+        multi_line: bool,
+    ) -> Result<Self, regex::Error> {
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .multi_line(multi_line)
+            .build()?;
+        Ok(Filter::Matches {
+            key,
+            regex: Arc::new(regex),
+        })
+    }
+
     /// Check if a page matches this filter.
     ///
     /// For link-based filters, requires access to the graph.
@@ -54,28 +115,123 @@ impl Filter {
                 .map(|values| values.iter().any(|v| v.contains(substring)))
                 .unwrap_or(false),
 
-            Filter::Matches { key, pattern } => {
-                match Regex::new(pattern) {
-                    Ok(re) => page
-                        .metadata
-                        .get(key)
-                        .map(|values| values.iter().any(|v| re.is_match(v)))
-                        .unwrap_or(false),
-                    Err(_) => false, // Invalid regex returns no match
-                }
-            }
+            Filter::Matches { key, regex } => page
+                .metadata
+                .get(key)
+                .map(|values| values.iter().any(|v| regex.is_match(v)))
+                .unwrap_or(false),
 
             Filter::LinksTo { page: target } => {
-                // Check if this page links to the target
-                graph.get_outlinks(&page.name).contains(target)
+                // Check if this page links to the target (navigational
+                // links only — an `![[Embed]]` transclusion doesn't count).
+                graph.get_navigational_outlinks(&page.name).contains(target)
             }
 
             Filter::LinkedFrom { page: source } => {
-                // Check if this page is linked from the source (has backlink)
-                graph.get_backlinks(&page.name).contains(source)
+                // Check if this page is linked from the source (has a
+                // navigational backlink; transclusions don't count).
+                graph.get_navigational_backlinks(&page.name).contains(source)
+            }
+
+            Filter::And(children) => children.iter().all(|f| f.matches_page(page, graph)),
+
+            Filter::Or(children) => children.iter().any(|f| f.matches_page(page, graph)),
+
+            Filter::Not(child) => !child.matches_page(page, graph),
+
+            Filter::Reachable {
+                page: target,
+                max_depth,
+                direction,
+            } => reachable_within(graph, target, *max_depth, *direction).contains(&page.name),
+        }
+    }
+}
+
+/// Bounded BFS over `WikiGraph` from `start`, following outlinks or backlinks.
+///
+/// Returns the set of page names reachable within `max_depth` hops
+/// (`None` means unbounded). `start` itself is not included.
+fn reachable_within(
+    graph: &WikiGraph,
+    start: &str,
+    max_depth: Option<usize>,
+    direction: LinkDirection,
+) -> std::collections::HashSet<String> {
+    use std::collections::VecDeque;
+
+    let max_depth = max_depth.unwrap_or(usize::MAX);
+    let mut visited = std::collections::HashSet::new();
+    let mut reached = std::collections::HashSet::new();
+    let mut queue = VecDeque::new();
+
+    visited.insert(start.to_string());
+    queue.push_back((start.to_string(), 0usize));
+
+    while let Some((name, depth)) = queue.pop_front() {
+        if depth >= max_depth {
+            continue;
+        }
+
+        let neighbors = match direction {
+            LinkDirection::Outlink => graph.get_navigational_outlinks(&name),
+            LinkDirection::Backlink => graph.get_navigational_backlinks(&name),
+        };
+
+        for neighbor in neighbors {
+            if visited.insert(neighbor.clone()) {
+                reached.insert(neighbor.clone());
+                queue.push_back((neighbor, depth + 1));
             }
         }
     }
+
+    reached
+}
+
+/// Collect every `Contains`/`Matches` filter targeting `column`, recursing
+/// into `And`/`Or`/`Not` combinators, for use by
+/// [`crate::graph::WikiGraph::metatable_highlighted`].
+pub fn collect_highlight_predicates<'a>(filter: &'a Filter, column: &str, out: &mut Vec<&'a Filter>) {
+    match filter {
+        Filter::Contains { key, .. } | Filter::Matches { key, .. } if key == column => {
+            out.push(filter)
+        }
+        Filter::And(children) | Filter::Or(children) => {
+            for child in children {
+                collect_highlight_predicates(child, column, out);
+            }
+        }
+        Filter::Not(child) => collect_highlight_predicates(child, column, out),
+        _ => {}
+    }
+}
+
+/// Run the first matching `Contains`/`Matches` predicate against `value`
+/// and, if it matches, return the value with the matched span wrapped in
+/// `open`/`close` delimiters plus the matched byte range.
+pub fn highlight_value(
+    value: &str,
+    predicates: &[&Filter],
+    open: &str,
+    close: &str,
+) -> Option<(String, (usize, usize))> {
+    for predicate in predicates {
+        let span = match predicate {
+            Filter::Contains { substring, .. } => value
+                .find(substring.as_str())
+                .map(|start| (start, start + substring.len())),
+            Filter::Matches { regex, .. } => regex.find(value).map(|m| (m.start(), m.end())),
+            _ => None,
+        };
+
+        if let Some((start, end)) = span {
+            let highlighted = format!("{}{}{}{}{}", &value[..start], open, &value[start..end], close, &value[end..]);
+            return Some((highlighted, (start, end)));
+        }
+    }
+
+    None
 }
 
 /// Check if a page matches all filters (AND logic).
@@ -159,20 +315,34 @@ impl PyFilter {
 
     /// Create a Matches filter: any value in metadata[key] matches regex.
     ///
+    /// The pattern is compiled immediately, so an invalid regex raises a
+    /// ValueError here rather than silently matching nothing later.
+    ///
     /// # Arguments
     /// * `key` - The metadata key to check
     /// * `pattern` - The regex pattern to match
+    /// * `case_insensitive` - Whether matching should ignore case
+    /// * `multi_line` - Whether `^`/`$` match at line boundaries
     ///
     /// # Example
     /// ```python
     /// # Find pages with version matching semver pattern
     /// filter = Filter.matches("version", r"v\d+\.\d+\.\d+")
+    ///
+    /// # Case-insensitive match
+    /// filter = Filter.matches("status", "draft", case_insensitive=True)
     /// ```
     #[staticmethod]
-    fn matches(key: String, pattern: String) -> Self {
-        Self {
-            inner: Filter::Matches { key, pattern },
-        }
+    #[pyo3(signature = (key, pattern, case_insensitive=false, multi_line=false))]
+    fn matches(
+        key: String,
+        pattern: &str,
+        case_insensitive: bool,
+        multi_line: bool,
+    ) -> PyResult<Self> {
+        let inner = Filter::matches(key, pattern, case_insensitive, multi_line)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid regex: {}", e)))?;
+        Ok(Self { inner })
     }
 
     /// Create a LinksTo filter: page links to the specified target.
@@ -209,18 +379,134 @@ impl PyFilter {
         }
     }
 
+    /// Create an And filter: all of the given filters must match.
+    ///
+    /// # Example
+    /// ```python
+    /// # status == draft AND author == alice
+    /// filter = Filter.and_([Filter.equals("status", "draft"), Filter.equals("author", "alice")])
+    /// ```
+    #[staticmethod]
+    fn and_(filters: Vec<PyFilter>) -> Self {
+        Self {
+            inner: Filter::And(filters.into_iter().map(|f| f.inner).collect()),
+        }
+    }
+
+    /// Create an Or filter: at least one of the given filters must match.
+    ///
+    /// # Example
+    /// ```python
+    /// # status == draft OR status == review
+    /// filter = Filter.or_([Filter.equals("status", "draft"), Filter.equals("status", "review")])
+    /// ```
+    #[staticmethod]
+    fn or_(filters: Vec<PyFilter>) -> Self {
+        Self {
+            inner: Filter::Or(filters.into_iter().map(|f| f.inner).collect()),
+        }
+    }
+
+    /// Create a Not filter: the given filter must not match.
+    ///
+    /// # Example
+    /// ```python
+    /// # NOT linked_from("Archive")
+    /// filter = Filter.not_(Filter.linked_from("Archive"))
+    /// ```
+    #[staticmethod]
+    fn not_(filter: PyFilter) -> Self {
+        Self {
+            inner: Filter::Not(Box::new(filter.inner)),
+        }
+    }
+
+    /// Create a Reachable filter matching descendants of `page` (via outlinks).
+    ///
+    /// # Arguments
+    /// * `page` - The page to measure reachability from
+    /// * `max_depth` - Maximum number of link hops, or None for unbounded
+    ///
+    /// # Example
+    /// ```python
+    /// # All pages reachable from HomePage within 2 hops
+    /// filter = Filter.reachable_to("HomePage", 2)
+    /// ```
+    #[staticmethod]
+    #[pyo3(signature = (page, max_depth=None))]
+    fn reachable_to(page: String, max_depth: Option<usize>) -> Self {
+        Self {
+            inner: Filter::Reachable {
+                page,
+                max_depth,
+                direction: LinkDirection::Outlink,
+            },
+        }
+    }
+
+    /// Create a Reachable filter matching ancestors of `page` (via backlinks).
+    ///
+    /// # Arguments
+    /// * `page` - The page to measure reachability from
+    /// * `max_depth` - Maximum number of link hops, or None for unbounded
+    ///
+    /// # Example
+    /// ```python
+    /// # All pages that can reach HomePage within 2 hops
+    /// filter = Filter.reachable_from("HomePage", 2)
+    /// ```
+    #[staticmethod]
+    #[pyo3(signature = (page, max_depth=None))]
+    fn reachable_from(page: String, max_depth: Option<usize>) -> Self {
+        Self {
+            inner: Filter::Reachable {
+                page,
+                max_depth,
+                direction: LinkDirection::Backlink,
+            },
+        }
+    }
+
     fn __repr__(&self) -> String {
-        match &self.inner {
-            Filter::Equals { key, value } => format!("Filter.equals('{}', '{}')", key, value),
-            Filter::HasKey { key } => format!("Filter.has_key('{}')", key),
-            Filter::Contains { key, substring } => {
-                format!("Filter.contains('{}', '{}')", key, substring)
-            }
-            Filter::Matches { key, pattern } => {
-                format!("Filter.matches('{}', '{}')", key, pattern)
+        repr_filter(&self.inner)
+    }
+}
+
+/// Render a Filter (including nested boolean combinators) as a Python-style repr.
+fn repr_filter(filter: &Filter) -> String {
+    match filter {
+        Filter::Equals { key, value } => format!("Filter.equals('{}', '{}')", key, value),
+        Filter::HasKey { key } => format!("Filter.has_key('{}')", key),
+        Filter::Contains { key, substring } => {
+            format!("Filter.contains('{}', '{}')", key, substring)
+        }
+        Filter::Matches { key, regex } => {
+            format!("Filter.matches('{}', '{}')", key, regex.as_str())
+        }
+        Filter::LinksTo { page } => format!("Filter.links_to('{}')", page),
+        Filter::LinkedFrom { page } => format!("Filter.linked_from('{}')", page),
+        Filter::And(children) => format!(
+            "Filter.and_([{}])",
+            children.iter().map(repr_filter).collect::<Vec<_>>().join(", ")
+        ),
+        Filter::Or(children) => format!(
+            "Filter.or_([{}])",
+            children.iter().map(repr_filter).collect::<Vec<_>>().join(", ")
+        ),
+        Filter::Not(child) => format!("Filter.not_({})", repr_filter(child)),
+        Filter::Reachable {
+            page,
+            max_depth,
+            direction,
+        } => {
+            let name = match direction {
+                LinkDirection::Outlink => "reachable_to",
+                LinkDirection::Backlink => "reachable_from",
+            };
+            match max_depth {
+                Some(depth) => format!("Filter.{}('{}', {})", name, page, depth),
+                None => format!("Filter.{}('{}', None)", name, page),
             }
-            Filter::LinksTo { page } => format!("Filter.links_to('{}')", page),
-            Filter::LinkedFrom { page } => format!("Filter.linked_from('{}')", page),
         }
     }
 }
@@ -236,6 +522,26 @@ pub struct MetaTableRow {
     /// Selected column values
     #[pyo3(get)]
     pub values: HashMap<String, Vec<String>>,
+
+    /// Shortest link-path distance to a ranking seed page, if the result
+    /// was produced by a ranked query. `None` means unranked or unreachable.
+    #[pyo3(get)]
+    pub distance: Option<usize>,
+
+    /// Number of distinct shortest/near-shortest paths found to the seed
+    /// page, if the result was produced by a ranked query with `k > 1`.
+    #[pyo3(get)]
+    pub path_count: Option<usize>,
+
+    /// Matched byte ranges per column, populated by a highlighted query
+    /// (see [`crate::graph::WikiGraph::metatable_highlighted`]). Empty
+    /// unless the query ran with a `Contains`/`Matches` filter on that column.
+    #[pyo3(get)]
+    pub highlights: HashMap<String, Vec<(usize, usize)>>,
+
+    /// Column values with the matched span wrapped in delimiters, populated
+    /// alongside `highlights`. Use [`MetaTableRow::get_highlighted`] to read.
+    pub highlighted_values: HashMap<String, Vec<String>>,
 }
 
 #[pymethods]
@@ -251,10 +557,23 @@ impl MetaTableRow {
         self.values.get(column).cloned().unwrap_or_default()
     }
 
+    /// Get a column's values with matched spans wrapped in highlight
+    /// delimiters. Falls back to the plain values if the column wasn't
+    /// highlighted by the query.
+    ///
+    /// # Arguments
+    /// * `column` - The column name to retrieve
+    fn get_highlighted(&self, column: &str) -> Vec<String> {
+        self.highlighted_values
+            .get(column)
+            .cloned()
+            .unwrap_or_else(|| self.get(column))
+    }
+
     fn __repr__(&self) -> String {
         format!(
-            "MetaTableRow(page='{}', values={:?})",
-            self.page_name, self.values
+            "MetaTableRow(page='{}', values={:?}, distance={:?})",
+            self.page_name, self.values, self.distance
         )
     }
 }
@@ -301,6 +620,72 @@ impl MetaTableResult {
     }
 }
 
+/// One distinct value of a facet key, with the pages carrying it.
+///
+/// Produced by [`crate::graph::WikiGraph::facet`]. Multi-value metadata
+/// fields are exploded before bucketing, so a page with three tags
+/// contributes to three buckets.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct FacetBucket {
+    /// The distinct metadata value this bucket counts.
+    #[pyo3(get)]
+    pub value: String,
+
+    /// Number of pages carrying this value (same as `pages.len()`).
+    #[pyo3(get)]
+    pub count: usize,
+
+    /// Names of the pages carrying this value.
+    #[pyo3(get)]
+    pub pages: Vec<String>,
+}
+
+#[pymethods]
+impl FacetBucket {
+    fn __repr__(&self) -> String {
+        format!(
+            "FacetBucket(value='{}', count={})",
+            self.value, self.count
+        )
+    }
+}
+
+/// Result of aggregating a single metadata key across all pages, as
+/// returned by [`crate::graph::WikiGraph::facet`].
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct FacetResult {
+    /// The metadata key that was aggregated (e.g. `"tags"`).
+    #[pyo3(get)]
+    pub key: String,
+
+    /// One bucket per distinct value, sorted alphabetically by value.
+    #[pyo3(get)]
+    pub buckets: Vec<FacetBucket>,
+}
+
+#[pymethods]
+impl FacetResult {
+    /// Get the number of distinct values.
+    fn __len__(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Check if no page carries this key.
+    fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "FacetResult(key='{}', buckets={})",
+            self.key,
+            self.buckets.len()
+        )
+    }
+}
+
 /// Iterator for MetaTableResult rows.
 #[pyclass]
 pub struct MetaTableRowIterator {
@@ -406,29 +791,35 @@ mod tests {
         let page = test_page("Test", vec![("version", vec!["v1.2.3"])]);
         let graph = WikiGraph::new();
 
-        let filter = Filter::Matches {
-            key: "version".to_string(),
-            pattern: r"v\d+\.\d+\.\d+".to_string(),
-        };
+        let filter = Filter::matches("version".to_string(), r"v\d+\.\d+\.\d+", false, false)
+            .expect("valid pattern");
         assert!(filter.matches_page(&page, &graph));
 
-        let filter = Filter::Matches {
-            key: "version".to_string(),
-            pattern: r"v\d+\.\d+\.\d+\.\d+".to_string(), // Requires 4 components
-        };
+        // Requires 4 components
+        let filter = Filter::matches("version".to_string(), r"v\d+\.\d+\.\d+\.\d+", false, false)
+            .expect("valid pattern");
         assert!(!filter.matches_page(&page, &graph));
     }
 
     #[test]
-    fn test_filter_invalid_regex() {
-        let page = test_page("Test", vec![("text", vec!["hello"])]);
+    fn test_filter_matches_case_insensitive() {
+        let page = test_page("Test", vec![("status", vec!["DRAFT"])]);
         let graph = WikiGraph::new();
 
-        let filter = Filter::Matches {
-            key: "text".to_string(),
-            pattern: r"[invalid".to_string(), // Unclosed bracket
-        };
-        assert!(!filter.matches_page(&page, &graph)); // Invalid regex = no match
+        let filter = Filter::matches("status".to_string(), "draft", false, false)
+            .expect("valid pattern");
+        assert!(!filter.matches_page(&page, &graph));
+
+        let filter = Filter::matches("status".to_string(), "draft", true, false)
+            .expect("valid pattern");
+        assert!(filter.matches_page(&page, &graph));
+    }
+
+    #[test]
+    fn test_filter_invalid_regex_rejected_at_construction() {
+        // Unclosed bracket: invalid regex is now reported immediately instead
+        // of silently matching nothing at query time.
+        assert!(Filter::matches("text".to_string(), r"[invalid", false, false).is_err());
     }
 
     #[test]
@@ -449,11 +840,9 @@ mod tests {
         }
         .matches_page(&page, &graph));
 
-        assert!(!Filter::Matches {
-            key: "missing".to_string(),
-            pattern: ".*".to_string()
-        }
-        .matches_page(&page, &graph));
+        assert!(!Filter::matches("missing".to_string(), ".*", false, false)
+            .expect("valid pattern")
+            .matches_page(&page, &graph));
     }
 
     #[test]
@@ -493,4 +882,189 @@ mod tests {
         // Empty filters = all pages match
         assert!(matches_all_filters(&page, &[], &graph));
     }
+
+    #[test]
+    fn test_filter_and() {
+        let page = test_page(
+            "Test",
+            vec![("status", vec!["draft"]), ("author", vec!["alice"])],
+        );
+        let graph = WikiGraph::new();
+
+        let filter = Filter::And(vec![
+            Filter::Equals {
+                key: "status".to_string(),
+                value: "draft".to_string(),
+            },
+            Filter::Equals {
+                key: "author".to_string(),
+                value: "alice".to_string(),
+            },
+        ]);
+        assert!(filter.matches_page(&page, &graph));
+
+        let filter = Filter::And(vec![
+            Filter::Equals {
+                key: "status".to_string(),
+                value: "draft".to_string(),
+            },
+            Filter::Equals {
+                key: "author".to_string(),
+                value: "bob".to_string(),
+            },
+        ]);
+        assert!(!filter.matches_page(&page, &graph));
+    }
+
+    #[test]
+    fn test_filter_or() {
+        let page = test_page("Test", vec![("status", vec!["draft"])]);
+        let graph = WikiGraph::new();
+
+        let filter = Filter::Or(vec![
+            Filter::Equals {
+                key: "status".to_string(),
+                value: "published".to_string(),
+            },
+            Filter::Equals {
+                key: "status".to_string(),
+                value: "draft".to_string(),
+            },
+        ]);
+        assert!(filter.matches_page(&page, &graph));
+
+        let filter = Filter::Or(vec![
+            Filter::Equals {
+                key: "status".to_string(),
+                value: "published".to_string(),
+            },
+            Filter::Equals {
+                key: "status".to_string(),
+                value: "review".to_string(),
+            },
+        ]);
+        assert!(!filter.matches_page(&page, &graph));
+    }
+
+    #[test]
+    fn test_filter_not() {
+        let page = test_page("Test", vec![("status", vec!["draft"])]);
+        let graph = WikiGraph::new();
+
+        let filter = Filter::Not(Box::new(Filter::Equals {
+            key: "status".to_string(),
+            value: "published".to_string(),
+        }));
+        assert!(filter.matches_page(&page, &graph));
+
+        let filter = Filter::Not(Box::new(Filter::Equals {
+            key: "status".to_string(),
+            value: "draft".to_string(),
+        }));
+        assert!(!filter.matches_page(&page, &graph));
+    }
+
+    #[test]
+    fn test_filter_nested_boolean_tree() {
+        // status = draft OR (status = review AND NOT linked_from Archive)
+        let page = test_page("Test", vec![("status", vec!["review"])]);
+        let graph = WikiGraph::new();
+
+        let filter = Filter::Or(vec![
+            Filter::Equals {
+                key: "status".to_string(),
+                value: "draft".to_string(),
+            },
+            Filter::And(vec![
+                Filter::Equals {
+                    key: "status".to_string(),
+                    value: "review".to_string(),
+                },
+                Filter::Not(Box::new(Filter::LinkedFrom {
+                    page: "Archive".to_string(),
+                })),
+            ]),
+        ]);
+        assert!(filter.matches_page(&page, &graph));
+    }
+
+    fn linear_graph() -> WikiGraph {
+        use crate::models::WikiLink;
+        use std::path::PathBuf;
+
+        let mut graph = WikiGraph::new();
+        for name in ["A", "B", "C", "D"] {
+            graph.add_page(PageNode::new(name.to_string(), PathBuf::from(format!("{}.md", name))));
+        }
+        // A -> B -> C -> D
+        graph.add_link("A", "B", WikiLink::new());
+        graph.add_link("B", "C", WikiLink::new());
+        graph.add_link("C", "D", WikiLink::new());
+        graph
+    }
+
+    #[test]
+    fn test_filter_reachable_to_unbounded() {
+        let graph = linear_graph();
+        let filter = Filter::Reachable {
+            page: "A".to_string(),
+            max_depth: None,
+            direction: LinkDirection::Outlink,
+        };
+
+        assert!(filter.matches_page(graph.get_page("B").unwrap(), &graph));
+        assert!(filter.matches_page(graph.get_page("D").unwrap(), &graph));
+        assert!(!filter.matches_page(graph.get_page("A").unwrap(), &graph));
+    }
+
+    #[test]
+    fn test_filter_reachable_to_depth_bound() {
+        let graph = linear_graph();
+        let filter = Filter::Reachable {
+            page: "A".to_string(),
+            max_depth: Some(1),
+            direction: LinkDirection::Outlink,
+        };
+
+        assert!(filter.matches_page(graph.get_page("B").unwrap(), &graph));
+        assert!(!filter.matches_page(graph.get_page("C").unwrap(), &graph));
+    }
+
+    #[test]
+    fn test_filter_reachable_from_ancestors() {
+        let graph = linear_graph();
+        let filter = Filter::Reachable {
+            page: "D".to_string(),
+            max_depth: None,
+            direction: LinkDirection::Backlink,
+        };
+
+        assert!(filter.matches_page(graph.get_page("A").unwrap(), &graph));
+        assert!(filter.matches_page(graph.get_page("C").unwrap(), &graph));
+        assert!(!filter.matches_page(graph.get_page("D").unwrap(), &graph));
+    }
+
+    #[test]
+    fn test_links_to_and_linked_from_ignore_transclusion_edges() {
+        use crate::models::WikiLink;
+        use std::path::PathBuf;
+
+        let mut graph = WikiGraph::new();
+        graph.add_page(PageNode::new("Index".to_string(), PathBuf::from("Index.md")));
+        graph.add_page(PageNode::new("About".to_string(), PathBuf::from("About.md")));
+        graph.add_link("Index", "About", WikiLink::transclusion(None));
+
+        let links_to = Filter::LinksTo { page: "About".to_string() };
+        assert!(!links_to.matches_page(graph.get_page("Index").unwrap(), &graph));
+
+        let linked_from = Filter::LinkedFrom { page: "Index".to_string() };
+        assert!(!linked_from.matches_page(graph.get_page("About").unwrap(), &graph));
+
+        let reachable = Filter::Reachable {
+            page: "Index".to_string(),
+            max_depth: None,
+            direction: LinkDirection::Outlink,
+        };
+        assert!(!reachable.matches_page(graph.get_page("About").unwrap(), &graph));
+    }
 }